@@ -1,6 +1,6 @@
 //! Error handling for the Demon language implementation.
 
-use crate::lexer::Token;
+use crate::lexer::{Span, Token};
 use crate::parser::Literal;
 use std::error::Error as StdError;
 use std::fmt;
@@ -42,6 +42,79 @@ impl RuntimeError {
     pub fn new(token: Token, message: String) -> Self {
         Self { token, message }
     }
+
+    /// Returns the source span where the error occurred.
+    pub fn span(&self) -> Span {
+        self.token.span
+    }
+
+    /// Renders this error together with the offending source line and a
+    /// `^^^` caret underline beneath its exact span, falling back to the
+    /// plain `Display` rendering when the span has no usable column
+    /// information (e.g. it was built from a default, column-less token).
+    pub fn report(&self, source: &str) -> String {
+        match render_caret(source, self.span()) {
+            Some(caret) => format!("{}\n{}", self, caret),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl ParseError {
+    /// Returns the source span where the error occurred, if the variant
+    /// carries a token. `Custom` errors have no associated token.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParseError::UnexpectedToken(token, _)
+            | ParseError::UnclosedParen(token)
+            | ParseError::UnclosedBrace(token)
+            | ParseError::ExpectedExpression(token)
+            | ParseError::ExpectedSemicolon(token)
+            | ParseError::ExpectedIdentifier(token)
+            | ParseError::ExpectedVariableName(token)
+            | ParseError::TooManyArguments(token, _, _)
+            | ParseError::InvalidAssignmentTarget(token)
+            | ParseError::ExpectedClass(token)
+            | ParseError::ExpectedSuperclass(token)
+            | ParseError::ExpectedMethod(token)
+            | ParseError::ExpectedProperty(token) => Some(token.span),
+            ParseError::Custom(_) => None,
+        }
+    }
+
+    /// Renders this error together with the offending source line and a
+    /// `^^^` caret underline beneath its exact span, falling back to the
+    /// plain `Display` rendering when no span is available (`Custom`
+    /// errors, or a span with no usable column information).
+    pub fn report(&self, source: &str) -> String {
+        match self.span().and_then(|span| render_caret(source, span)) {
+            Some(caret) => format!("{}\n{}", self, caret),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// Renders the source line containing `span` with a caret underline (`^`)
+/// beneath the columns it covers. Returns `None` if the span has no column
+/// (`start_col` 0, as on a token built without a span) or its line number
+/// is out of range for `source`, which is the signal callers use to fall
+/// back to a plain, location-free message.
+fn render_caret(source: &str, span: Span) -> Option<String> {
+    if span.start_col == 0 {
+        return None;
+    }
+    let line_text = source.lines().nth(span.start_line.checked_sub(1)?)?;
+    let start_col = span.start_col.max(1);
+    let width = if span.end_line == span.start_line && span.end_col > span.start_col {
+        span.end_col - span.start_col
+    } else {
+        1
+    };
+
+    let mut rendered = format!("{}\n", line_text);
+    rendered.push_str(&" ".repeat(start_col - 1));
+    rendered.push_str(&"^".repeat(width));
+    Some(rendered)
 }
 
 /// A general error type that can represent both parse and runtime errors.
@@ -200,6 +273,36 @@ impl From<RuntimeError> for InterpreterError {
     }
 }
 
+impl InterpreterError {
+    /// Returns the source span where the error occurred, if one is known.
+    /// `Return`, `Break`, `Continue`, and `General` carry no location.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            InterpreterError::Parse(err) => err.span(),
+            InterpreterError::Runtime(err) => Some(err.span()),
+            InterpreterError::Return(_)
+            | InterpreterError::Break
+            | InterpreterError::Continue
+            | InterpreterError::General(_) => None,
+        }
+    }
+
+    /// Renders this error together with the offending source line and a
+    /// caret underline beneath its exact span, the same way `ParseError`
+    /// and `RuntimeError` do; falls back to the plain `Display` rendering
+    /// for variants with no associated source location.
+    pub fn report(&self, source: &str) -> String {
+        match self {
+            InterpreterError::Parse(err) => err.report(source),
+            InterpreterError::Runtime(err) => err.report(source),
+            InterpreterError::Return(_)
+            | InterpreterError::Break
+            | InterpreterError::Continue
+            | InterpreterError::General(_) => self.to_string(),
+        }
+    }
+}
+
 /// Creates a new runtime error with the given token and message.
 pub fn runtime_error(token: &Token, message: &str) -> RuntimeError {
     RuntimeError {
@@ -0,0 +1,109 @@
+//! String interning for identifiers.
+//!
+//! `Environment` used to key its variable table by `String`, which means
+//! every `define`/`get`/`assign` hashed and often cloned a heap-allocated
+//! lexeme. Interning turns each distinct identifier into a small `Copy`
+//! `Symbol`, so repeated lookups of the same name hash/copy a `u32` instead.
+//!
+//! Interning happens once per identifier, at scan time: [`Scanner`] calls
+//! [`intern`] while building each [`Token`], so `Token::symbol` is already
+//! resolved by the time the parser or interpreter ever sees it. Everything
+//! downstream (`Environment::get`/`assign`) reads `token.symbol` directly
+//! instead of re-interning the lexeme on every access.
+//!
+//! [`Scanner`]: crate::lexer::Scanner
+//! [`Token`]: crate::lexer::Token
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// A small, `Copy` handle standing in for an interned identifier string.
+/// Two symbols are equal exactly when the strings they were interned from
+/// are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+/// Assigns each unique string a `Symbol`, backed by a `HashMap` for the
+/// string-to-symbol direction and a `Vec` for the reverse lookup needed by
+/// error messages and `Display`.
+#[derive(Debug, Default)]
+pub struct Interner {
+    map: HashMap<String, Symbol>,
+    strings: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            strings: Vec::new(),
+        }
+    }
+
+    /// Returns the `Symbol` for `name`, interning it if this is the first
+    /// time it's been seen.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.map.get(name) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(self.strings.len() as u32);
+        self.strings.push(name.to_string());
+        self.map.insert(name.to_string(), symbol);
+        symbol
+    }
+
+    /// Resolves a `Symbol` back to the string it was interned from. Panics
+    /// if given a `Symbol` this interner didn't produce, which should never
+    /// happen since `Symbol`s are only ever created by `intern`.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+}
+
+fn global_interner() -> &'static Mutex<Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(|| Mutex::new(Interner::new()))
+}
+
+/// Interns `name` in the process-wide interner shared by every scan.
+///
+/// This is what lets a `Symbol` computed once at scan time (on `Token`)
+/// stay meaningful anywhere else that needs to intern the same name, such
+/// as a synthetic token built without going through the scanner.
+pub fn intern(name: &str) -> Symbol {
+    global_interner().lock().unwrap().intern(name)
+}
+
+/// Resolves a `Symbol` back to the string it was interned from.
+pub fn resolve(symbol: Symbol) -> String {
+    global_interner().lock().unwrap().resolve(symbol).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning_same_string_returns_same_symbol() {
+        let mut interner = Interner::new();
+        let a = interner.intern("foo");
+        let b = interner.intern("foo");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_interning_different_strings_returns_different_symbols() {
+        let mut interner = Interner::new();
+        let a = interner.intern("foo");
+        let b = interner.intern("bar");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_resolve_round_trips() {
+        let mut interner = Interner::new();
+        let symbol = interner.intern("hello");
+        assert_eq!(interner.resolve(symbol), "hello");
+    }
+}
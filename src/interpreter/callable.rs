@@ -8,11 +8,52 @@ use crate::parser::Literal;
 
 pub trait Callable: fmt::Debug + CallableClone {
     fn as_any(&self) -> &dyn Any;
-    fn arity(&self) -> usize;
+    fn arity(&self) -> Arity;
     fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Literal>) -> Result<Literal>;
     fn to_string(&self) -> String;
 }
 
+/// How many arguments a `Callable` accepts. Plain `usize` arity can't
+/// express a function with optional or trailing variadic arguments (e.g.
+/// `range(start, end[, step])`), so call sites check `accepts` instead of
+/// equality against a single number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    /// Exactly `n` arguments.
+    Exact(usize),
+    /// Between `min` and `max` arguments, inclusive.
+    Range(usize, usize),
+    /// `min` or more arguments, with no upper bound.
+    AtLeast(usize),
+}
+
+impl Arity {
+    /// Whether a call with `n` arguments satisfies this arity.
+    pub fn accepts(&self, n: usize) -> bool {
+        match self {
+            Arity::Exact(k) => n == *k,
+            Arity::Range(min, max) => (*min..=*max).contains(&n),
+            Arity::AtLeast(min) => n >= *min,
+        }
+    }
+}
+
+impl From<usize> for Arity {
+    fn from(n: usize) -> Self {
+        Arity::Exact(n)
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exact(n) => write!(f, "{}", n),
+            Arity::Range(min, max) => write!(f, "{} to {}", min, max),
+            Arity::AtLeast(min) => write!(f, "at least {}", min),
+        }
+    }
+}
+
 // Helper trait for object-safe cloning
 pub trait CallableClone {
     fn clone_box(&self) -> Box<dyn Callable>;
@@ -37,7 +78,7 @@ impl Clone for Box<dyn Callable> {
 #[derive(Clone)]
 pub struct NativeFunction {
     pub name: String,
-    pub arity: usize,
+    pub arity: Arity,
     pub func: Rc<dyn Fn(&mut Interpreter, Vec<Literal>) -> Result<Literal>>,
 }
 
@@ -57,7 +98,7 @@ impl Callable for NativeFunction {
         self
     }
 
-    fn arity(&self) -> usize {
+    fn arity(&self) -> Arity {
         self.arity
     }
 
@@ -71,13 +112,13 @@ impl Callable for NativeFunction {
 }
 
 impl NativeFunction {
-    pub fn new<F>(name: &str, arity: usize, func: F) -> Self
+    pub fn new<F>(name: &str, arity: impl Into<Arity>, func: F) -> Self
     where
         F: 'static + Fn(&mut Interpreter, Vec<Literal>) -> Result<Literal>,
     {
         NativeFunction {
             name: name.to_string(),
-            arity,
+            arity: arity.into(),
             func: Rc::new(func),
         }
     }
@@ -4,7 +4,7 @@ use std::fmt;
 use std::rc::Rc;
 
 use crate::error::{Result, RuntimeError};
-use crate::interpreter::{Callable, Function};
+use crate::interpreter::{Arity, Callable, Function};
 use crate::lexer::Token;
 use crate::parser::Literal;
 
@@ -46,11 +46,11 @@ impl Callable for Class {
         self
     }
 
-    fn arity(&self) -> usize {
+    fn arity(&self) -> Arity {
         if let Some(initializer) = self.find_method("init") {
             initializer.arity()
         } else {
-            0
+            Arity::Exact(0)
         }
     }
 
@@ -106,6 +106,19 @@ impl Instance {
     pub fn set(&mut self, name: Token, value: Literal) {
         self.fields.insert(name.lexeme, value);
     }
+
+    /// Looks up a "magic" method (`to_string`, `equals`, `add`, `sub`,
+    /// `mul`, `get`, `set`) on this instance's class and, if present, binds
+    /// it to `self_ref`. Returns `None` when the class defines no such
+    /// method, so callers fall back to their own built-in behavior.
+    ///
+    /// This only binds the method; it doesn't call it, so callers can drop
+    /// any borrow they hold on this `Instance` first (mirroring how `get`
+    /// binds a plain method without invoking it) before running user code
+    /// that might re-borrow the same instance.
+    pub fn find_magic_method(&self, self_ref: &Literal, name: &str) -> Option<Function> {
+        self.class.find_method(name).map(|method| method.bind(self_ref.clone()))
+    }
 }
 
 impl fmt::Debug for Instance {
@@ -1,14 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::cell::RefCell;
 
 use crate::error::{Result, Error, RuntimeError};
+use crate::interner::Symbol;
 use crate::lexer::Token;
 use crate::parser::Literal;
 
+/// Maps variable names to values.
+///
+/// Names are interned to a small `Copy` `Symbol` once, at scan time (see
+/// [`Token::symbol`]), so `define`/`get`/`assign` all key off a `u32` read
+/// straight from the token instead of hashing/interning the lexeme on every
+/// access.
 #[derive(Debug, Clone)]
 pub struct Environment {
-    values: HashMap<String, Literal>,
+    values: HashMap<Symbol, Literal>,
+    /// Symbols defined with `const` rather than `var`, in this scope only.
+    /// Checked by `assign`/`assign_at` before overwriting a binding;
+    /// shadowing a const with a new `var`/`const` in an inner scope is still
+    /// fine, since that's a different `(Symbol, Environment)` pair.
+    consts: HashSet<Symbol>,
     pub enclosing: Option<Rc<RefCell<Environment>>>,
 }
 
@@ -16,6 +28,7 @@ impl Environment {
     pub fn new() -> Self {
         Environment {
             values: HashMap::new(),
+            consts: HashSet::new(),
             enclosing: None,
         }
     }
@@ -23,16 +36,25 @@ impl Environment {
     pub fn with_enclosing(enclosing: Rc<RefCell<Environment>>) -> Self {
         Environment {
             values: HashMap::new(),
+            consts: HashSet::new(),
             enclosing: Some(enclosing),
         }
     }
 
-    pub fn define(&mut self, name: String, value: Literal) {
-        self.values.insert(name, value);
+    pub fn define(&mut self, symbol: Symbol, value: Literal) {
+        self.consts.remove(&symbol);
+        self.values.insert(symbol, value);
+    }
+
+    /// Defines a `const` binding: like `define`, but `assign`/`assign_at`
+    /// will reject later writes to it.
+    pub fn define_const(&mut self, symbol: Symbol, value: Literal) {
+        self.values.insert(symbol, value);
+        self.consts.insert(symbol);
     }
 
     pub fn get(&self, name: &Token) -> Result<Literal> {
-        if let Some(value) = self.values.get(&name.lexeme) {
+        if let Some(value) = self.values.get(&name.symbol) {
             return Ok(value.clone());
         }
 
@@ -47,8 +69,14 @@ impl Environment {
     }
 
     pub fn assign(&mut self, name: &Token, value: Literal) -> Result<()> {
-        if self.values.contains_key(&name.lexeme) {
-            self.values.insert(name.lexeme.clone(), value);
+        if self.values.contains_key(&name.symbol) {
+            if self.consts.contains(&name.symbol) {
+                return Err(Error::Runtime(RuntimeError::new(
+                    name.clone(),
+                    format!("Cannot assign to constant '{}'.", name.lexeme),
+                )));
+            }
+            self.values.insert(name.symbol, value);
             return Ok(());
         }
 
@@ -62,13 +90,15 @@ impl Environment {
         )))
     }
 
-    pub fn get_at(&self, distance: usize, name: &str) -> Result<Literal> {
+    pub fn get_at(&self, distance: usize, symbol: Symbol) -> Result<Literal> {
         if distance == 0 {
-            self.values.get(name)
+            self.values.get(&symbol)
                 .cloned()
-                .ok_or_else(|| Error::General(format!("Undefined variable '{}'.", name)))
+                .ok_or_else(|| Error::General(format!(
+                    "Undefined variable '{}'.", crate::interner::resolve(symbol),
+                )))
         } else if let Some(enclosing) = &self.enclosing {
-            enclosing.borrow().get_at(distance - 1, name)
+            enclosing.borrow().get_at(distance - 1, symbol)
         } else {
             Err(Error::General("Invalid environment depth.".to_string()))
         }
@@ -76,7 +106,13 @@ impl Environment {
 
     pub fn assign_at(&mut self, distance: usize, name: &Token, value: Literal) -> Result<()> {
         if distance == 0 {
-            self.values.insert(name.lexeme.clone(), value);
+            if self.consts.contains(&name.symbol) {
+                return Err(Error::Runtime(RuntimeError::new(
+                    name.clone(),
+                    format!("Cannot assign to constant '{}'.", name.lexeme),
+                )));
+            }
+            self.values.insert(name.symbol, value);
             Ok(())
         } else if let Some(enclosing) = &mut self.enclosing {
             enclosing.borrow_mut().assign_at(distance - 1, name, value)
@@ -4,7 +4,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 
 use crate::error::{InterpreterError, Result};
-use crate::interpreter::{Environment, Interpreter, Callable};
+use crate::interpreter::{Arity, Environment, Interpreter, Callable};
 use crate::parser::{Stmt, Literal};
 
 #[derive(Clone)]
@@ -25,7 +25,7 @@ impl Function {
 
     pub fn bind(&self, instance: Literal) -> Self {
         let environment = Rc::new(RefCell::new(Environment::with_enclosing(Rc::clone(&self.closure))));
-        environment.borrow_mut().define("this".to_string(), instance);
+        environment.borrow_mut().define(crate::interner::intern("this"), instance);
         
         Function::new(
             Rc::clone(&self.declaration),
@@ -40,10 +40,10 @@ impl Callable for Function {
         self
     }
 
-    fn arity(&self) -> usize {
+    fn arity(&self) -> Arity {
         match &*self.declaration {
-            Stmt::Function { params, .. } => params.len(),
-            _ => 0,
+            Stmt::Function { params, .. } => Arity::Exact(params.len()),
+            _ => Arity::Exact(0),
         }
     }
 
@@ -52,7 +52,11 @@ impl Callable for Function {
             Environment::with_enclosing(Rc::clone(&self.closure))
         ));
 
-        // Clone the params and body from the Rc<Stmt> without moving
+        // `body` is `Rc`-shared, so this clone just bumps a refcount — it
+        // keeps pointing at the exact statements `resolver::resolve` saw,
+        // which is what lets `Interpreter::locals` (keyed by `Expr` address)
+        // actually hit on every call instead of silently falling back to a
+        // dynamic environment walk.
         let (params, body) = match &*self.declaration {
             Stmt::Function { params, body, .. } => (params.clone(), body.clone()),
             _ => unreachable!("Function declaration expected"),
@@ -61,13 +65,13 @@ impl Callable for Function {
         for (i, param) in params.iter().enumerate() {
             environment
                 .borrow_mut()
-                .define(param.lexeme.clone(), arguments[i].clone());
+                .define(param.symbol, arguments[i].clone());
         }
 
         match interpreter.execute_block(&body, environment) {
             Ok(()) => {
                 if self.is_initializer {
-                    self.closure.borrow().get_at(0, "this")
+                    self.closure.borrow().get_at(0, crate::interner::intern("this"))
                 } else {
                     Ok(Literal::Nil)
                 }
@@ -75,7 +79,7 @@ impl Callable for Function {
             Err(e) => match e {
                 InterpreterError::Return(value) => {
                     if self.is_initializer {
-                        self.closure.borrow().get_at(0, "this")
+                        self.closure.borrow().get_at(0, crate::interner::intern("this"))
                     } else {
                         Ok(value)
                     }
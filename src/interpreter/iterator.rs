@@ -0,0 +1,46 @@
+//! Lazy iterator values, the backing representation for `Literal::Iterator`.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::error::Result;
+use crate::interpreter::Interpreter;
+use crate::parser::Literal;
+
+/// A lazy, pull-based sequence. Each call to `next` advances the underlying
+/// producer by one step and returns the value it yielded, or `None` once
+/// exhausted.
+///
+/// The producer is reference-counted and wrapped in a `RefCell` rather than
+/// owned directly, because combinators like `filter_iter`/`map_iter`/`take` build
+/// a new `LazyIter` that closes over its *source* `LazyIter`, and `Literal`
+/// needs to stay `Clone` so an iterator value can be bound to a variable or
+/// passed as an argument like any other value. The producer takes
+/// `&mut Interpreter` because combinators are re-entrant: their user-supplied
+/// callback (`fn`) is itself called back through `Callable::call`, which
+/// needs the interpreter.
+#[derive(Clone)]
+pub struct LazyIter(Rc<RefCell<dyn FnMut(&mut Interpreter) -> Result<Option<Literal>>>>);
+
+impl LazyIter {
+    /// Wraps a producer closure as a lazy iterator.
+    pub fn new<F>(producer: F) -> Self
+    where
+        F: 'static + FnMut(&mut Interpreter) -> Result<Option<Literal>>,
+    {
+        LazyIter(Rc::new(RefCell::new(producer)))
+    }
+
+    /// Pulls the next value from the sequence, if any.
+    pub fn next(&self, interpreter: &mut Interpreter) -> Result<Option<Literal>> {
+        let mut producer = self.0.borrow_mut();
+        (*producer)(interpreter)
+    }
+}
+
+impl fmt::Debug for LazyIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<iterator>")
+    }
+}
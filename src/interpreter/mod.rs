@@ -5,6 +5,8 @@ mod callable;
 mod class;
 mod environment;
 mod function;
+mod iterator;
+mod rng;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -14,16 +16,19 @@ use crate::lexer::{Token, TokenType};
 use crate::parser::{Expr, Stmt};
 use crate::parser::Literal;
 
-pub use callable::{Callable, NativeFunction};
+pub use callable::{Arity, Callable, NativeFunction};
 pub use class::{Class, Instance};
 pub use environment::Environment;
 pub use function::Function;
+pub use iterator::LazyIter;
+pub use rng::Rng;
 
 /// The main interpreter for the Demon language.
 pub struct Interpreter {
     globals: Rc<RefCell<Environment>>,
     environment: Rc<RefCell<Environment>>,
     locals: std::collections::HashMap<usize, usize>,
+    rng: Rng,
 }
 
 impl Default for Interpreter {
@@ -34,6 +39,7 @@ impl Default for Interpreter {
             globals: Rc::clone(&globals),
             environment,
             locals: std::collections::HashMap::new(),
+            rng: Rng::new(),
         };
 
         // Add the clock function (kept for backward compatibility)
@@ -48,7 +54,7 @@ impl Default for Interpreter {
 
         interpreter.globals
             .borrow_mut()
-            .define("clock".to_string(), Literal::Callable(Box::new(clock)));
+            .define(crate::interner::intern("clock"), Literal::Callable(Box::new(clock)));
 
         interpreter
     }
@@ -60,15 +66,29 @@ impl Interpreter {
         self.globals.clone()
     }
 
+    /// Loads the scope depths computed by `resolver::resolve`, so that
+    /// `look_up_variable` can jump straight to the right environment instead
+    /// of falling back to a linear walk through enclosing scopes.
+    pub fn load_resolution(&mut self, locals: std::collections::HashMap<usize, usize>) {
+        self.locals = locals;
+    }
+
     /// Creates a new interpreter with the given environment.
     pub fn with_environment(environment: Rc<RefCell<Environment>>) -> Self {
         Self {
             globals: Rc::clone(&environment),
             environment,
             locals: std::collections::HashMap::new(),
+            rng: Rng::new(),
         }
     }
 
+    /// Returns a mutable reference to the interpreter's random number
+    /// generator, so native functions can draw from and reseed it.
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+
     /// Interprets a list of statements.
     pub fn interpret(&mut self, statements: &[Stmt]) -> Result<()> {
         for statement in statements {
@@ -87,7 +107,8 @@ impl Interpreter {
             }
             Stmt::Print(expr) => {
                 let value = self.evaluate(expr)?;
-                println!("{}", value);
+                let text = crate::stdlib::display(self, &value)?;
+                println!("{}", text);
                 Ok(())
             }
             Stmt::Var { name, initializer } => {
@@ -99,14 +120,14 @@ impl Interpreter {
 
                 self.environment
                     .borrow_mut()
-                    .define(name.lexeme.clone(), value);
+                    .define(name.symbol, value);
                 Ok(())
             }
             Stmt::Const { name, initializer } => {
                 let value = self.evaluate(initializer)?;
                 self.environment
                     .borrow_mut()
-                    .define(name.lexeme.clone(), value);
+                    .define_const(name.symbol, value);
                 Ok(())
             }
             Stmt::Block(statements) => {
@@ -134,10 +155,56 @@ impl Interpreter {
                     let condition_value = self.evaluate(condition)?;
                     self.is_truthy(&condition_value)
                 } {
-                    self.execute(body)?;
+                    match self.execute(body) {
+                        Ok(()) => {}
+                        Err(InterpreterError::Break) => break,
+                        Err(InterpreterError::Continue) => continue,
+                        Err(e) => return Err(e),
+                    }
                 }
                 Ok(())
             }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                let for_env = Rc::new(RefCell::new(Environment::with_enclosing(Rc::clone(
+                    &self.environment,
+                ))));
+                let previous = std::mem::replace(&mut self.environment, for_env);
+                let result = (|| {
+                    if let Some(init) = initializer {
+                        self.execute(init)?;
+                    }
+
+                    loop {
+                        if let Some(condition) = condition {
+                            let condition_value = self.evaluate(condition)?;
+                            if !self.is_truthy(&condition_value) {
+                                break;
+                            }
+                        }
+
+                        match self.execute(body) {
+                            Ok(()) => {}
+                            Err(InterpreterError::Break) => break,
+                            Err(InterpreterError::Continue) => {}
+                            Err(e) => return Err(e),
+                        }
+
+                        if let Some(increment) = increment {
+                            self.evaluate(increment)?;
+                        }
+                    }
+                    Ok(())
+                })();
+                self.environment = previous;
+                result
+            }
+            Stmt::Break(_) => Err(InterpreterError::Break),
+            Stmt::Continue(_) => Err(InterpreterError::Continue),
             Stmt::Function {
                 name, ..
             } => {
@@ -148,7 +215,7 @@ impl Interpreter {
                 );
                 self.environment
                     .borrow_mut()
-                    .define(name.lexeme.clone(), Literal::Callable(Box::new(function)));
+                    .define(name.symbol, Literal::Callable(Box::new(function)));
                 Ok(())
             }
             Stmt::Return { value, .. } => {
@@ -229,32 +296,35 @@ impl Interpreter {
                 let right = self.evaluate(right)?;
 
                 match (&left, &operator.token_type, &right) {
-                    (Literal::Number(a), TokenType::Plus, Literal::Number(b)) => {
-                        Ok(Literal::Number(a + b))
-                    }
                     (Literal::String(a), TokenType::Plus, Literal::String(b)) => {
                         Ok(Literal::String(format!("{}{}", a, b)))
                     }
-                    (Literal::Number(a), TokenType::Minus, Literal::Number(b)) => {
-                        Ok(Literal::Number(a - b))
+                    (_, TokenType::Plus, _) if left.is_numeric_tower() && right.is_numeric_tower() => {
+                        left.numeric_add(&right)
                     }
-                    (Literal::Number(a), TokenType::Star, Literal::Number(b)) => {
-                        Ok(Literal::Number(a * b))
+                    (_, TokenType::Minus, _) if left.is_numeric_tower() && right.is_numeric_tower() => {
+                        left.numeric_sub(&right)
                     }
-                    (Literal::Number(a), TokenType::Slash, Literal::Number(b)) => {
-                        if *b == 0.0 {
-                            return Err(InterpreterError::Runtime(RuntimeError::new(
-                                operator.clone(),
-                                "Division by zero.".to_string(),
-                            )));
-                        }
-                        Ok(Literal::Number(a / b))
+                    (_, TokenType::Star, _) if left.is_numeric_tower() && right.is_numeric_tower() => {
+                        left.numeric_mul(&right)
+                    }
+                    (_, TokenType::Slash, _) if left.is_numeric_tower() && right.is_numeric_tower() => {
+                        left.numeric_div(&right)
+                    }
+                    (Literal::Instance(_), TokenType::Plus, _) => {
+                        self.call_operator_method(&left, "add", right.clone(), operator)
+                    }
+                    (Literal::Instance(_), TokenType::Minus, _) => {
+                        self.call_operator_method(&left, "sub", right.clone(), operator)
+                    }
+                    (Literal::Instance(_), TokenType::Star, _) => {
+                        self.call_operator_method(&left, "mul", right.clone(), operator)
                     }
                     (_, TokenType::EqualEqual, _) => {
-                        Ok(Literal::Boolean(self.is_equal(&left, &right)))
+                        Ok(Literal::Boolean(self.is_equal(&left, &right)?))
                     }
                     (_, TokenType::BangEqual, _) => {
-                        Ok(Literal::Boolean(!self.is_equal(&left, &right)))
+                        Ok(Literal::Boolean(!self.is_equal(&left, &right)?))
                     }
                     (Literal::Number(a), TokenType::Greater, Literal::Number(b)) => {
                         Ok(Literal::Boolean(a > b))
@@ -311,7 +381,7 @@ impl Interpreter {
                 }
 
                 if let Literal::Callable(function) = callee {
-                    if args.len() != function.arity() {
+                    if !function.arity().accepts(args.len()) {
                         return Err(InterpreterError::Runtime(RuntimeError::new(
                             arguments[0].first_token(),
                             format!("Expected {} arguments but got {}.", function.arity(), args.len()),
@@ -353,10 +423,44 @@ impl Interpreter {
                     .into())
                 }
             },
+            Expr::ArrayAccess { array, index } => {
+                let array_value = self.evaluate(array)?;
+                let index_value = self.evaluate(index)?;
+
+                if let Literal::Instance(instance) = &array_value {
+                    let bound = instance.borrow().find_magic_method(&array_value, "get");
+                    if let Some(method) = bound {
+                        return method.call(self, vec![index_value]);
+                    }
+                }
+
+                Err(InterpreterError::Runtime(RuntimeError::new(
+                    expr.first_token(),
+                    "Only instances with a 'get' method support index access.".to_string(),
+                )))
+            }
+            Expr::IndexSet { object, index, value } => {
+                let value = self.evaluate(value)?;
+                let object_value = self.evaluate(object)?;
+                let index_value = self.evaluate(index)?;
+
+                if let Literal::Instance(instance) = &object_value {
+                    let bound = instance.borrow().find_magic_method(&object_value, "set");
+                    if let Some(method) = bound {
+                        method.call(self, vec![index_value, value.clone()])?;
+                        return Ok(value);
+                    }
+                }
+
+                Err(InterpreterError::Runtime(RuntimeError::new(
+                    expr.first_token(),
+                    "Only instances with a 'set' method support index assignment.".to_string(),
+                )))
+            }
             Expr::Variable(name) => self.look_up_variable(name, expr),
             Expr::Assign { name, value } => {
                 let value = self.evaluate(value)?;
-                self.environment.borrow_mut().assign(name, value.clone())?;
+                self.assign_variable(name, expr, value.clone())?;
                 Ok(value)
             },
             Expr::This(keyword) => self.look_up_variable(keyword, expr),
@@ -393,6 +497,144 @@ impl Interpreter {
                     .into())
                 }
             },
+            Expr::Lambda { params, body } => {
+                let name = Token::new(
+                    TokenType::Identifier("<lambda>".to_string()),
+                    "<lambda>".to_string(),
+                    params.first().map(|p| p.line).unwrap_or(0),
+                );
+                let declaration = Stmt::Function {
+                    name,
+                    params: params.clone(),
+                    body: Rc::clone(body),
+                };
+                let function = Function::new(Rc::new(declaration), Rc::clone(&self.environment), false);
+                Ok(Literal::Callable(Box::new(function)))
+            }
+            Expr::Pipeline { value, func, fold } if *fold => {
+                // `x |: f(a)` appends `x` as a trailing argument to the
+                // call on the right, `f(a, x)`, rather than calling the
+                // right-hand side with `x` alone.
+                let arg = self.evaluate(value)?;
+                let Expr::Call { callee, arguments } = func.as_ref() else {
+                    return Err(InterpreterError::Runtime(RuntimeError::new(
+                        expr.first_token(),
+                        "Right-hand side of '|:' must be a call.".to_string(),
+                    )));
+                };
+
+                let callee = self.evaluate(callee)?;
+                let mut args = Vec::with_capacity(arguments.len() + 1);
+                for argument in arguments {
+                    args.push(self.evaluate(argument)?);
+                }
+                args.push(arg);
+
+                if let Literal::Callable(callable) = callee {
+                    if !callable.arity().accepts(args.len()) {
+                        return Err(InterpreterError::Runtime(RuntimeError::new(
+                            expr.first_token(),
+                            format!("Expected {} arguments but got {}.", callable.arity(), args.len()),
+                        )));
+                    }
+                    callable.call(self, args)
+                } else {
+                    Err(InterpreterError::Runtime(RuntimeError::new(
+                        expr.first_token(),
+                        "Right-hand side of '|:' must be callable.".to_string(),
+                    )))
+                }
+            }
+            Expr::Pipeline { value, func, .. } => {
+                let arg = self.evaluate(value)?;
+                let func = self.evaluate(func)?;
+
+                if let Literal::Callable(callable) = func {
+                    if !callable.arity().accepts(1) {
+                        return Err(InterpreterError::Runtime(RuntimeError::new(
+                            expr.first_token(),
+                            format!("Expected {} arguments but got 1.", callable.arity()),
+                        )));
+                    }
+                    callable.call(self, vec![arg])
+                } else {
+                    Err(InterpreterError::Runtime(RuntimeError::new(
+                        expr.first_token(),
+                        "Right-hand side of '|>' must be callable.".to_string(),
+                    )))
+                }
+            }
+            Expr::New { class, arguments } => {
+                let callee = self.evaluate(class)?;
+                let mut args = Vec::new();
+                for argument in arguments {
+                    args.push(self.evaluate(argument)?);
+                }
+
+                if let Literal::Callable(function) = callee {
+                    if !function.arity().accepts(args.len()) {
+                        return Err(InterpreterError::Runtime(RuntimeError::new(
+                            expr.first_token(),
+                            format!("Expected {} arguments but got {}.", function.arity(), args.len()),
+                        )));
+                    }
+                    function.call(self, args)
+                } else {
+                    Err(InterpreterError::Runtime(RuntimeError::new(
+                        expr.first_token(),
+                        "Can only use 'new' with a class.".to_string(),
+                    )))
+                }
+            }
+            Expr::CustomNew {
+                allocator,
+                class,
+                arguments,
+            } => {
+                let allocator = match self.evaluate(allocator)? {
+                    Literal::Allocator(allocator) => allocator,
+                    _ => {
+                        return Err(InterpreterError::Runtime(RuntimeError::new(
+                            expr.first_token(),
+                            "Allocator expression must evaluate to an allocator.".to_string(),
+                        )))
+                    }
+                };
+
+                let callee = self.evaluate(class)?;
+                let mut args = Vec::new();
+                for argument in arguments {
+                    args.push(self.evaluate(argument)?);
+                }
+
+                if let Literal::Callable(function) = callee {
+                    if !function.arity().accepts(args.len()) {
+                        return Err(InterpreterError::Runtime(RuntimeError::new(
+                            expr.first_token(),
+                            format!("Expected {} arguments but got {}.", function.arity(), args.len()),
+                        )));
+                    }
+
+                    // Charge the instance's footprint to the chosen
+                    // allocator before constructing it. Instances are
+                    // still the interpreter's usual `Rc<RefCell<Instance>>`
+                    // (there's no raw-pointer object model to place them
+                    // behind), so this routes the allocation bookkeeping
+                    // through the arena without the instance literally
+                    // living at the returned address.
+                    let layout = std::alloc::Layout::new::<Instance>();
+                    unsafe {
+                        allocator.allocate(layout);
+                    }
+
+                    function.call(self, args)
+                } else {
+                    Err(InterpreterError::Runtime(RuntimeError::new(
+                        expr.first_token(),
+                        "Can only use 'new' with a class.".to_string(),
+                    )))
+                }
+            }
             _ => Err(InterpreterError::Runtime(RuntimeError::new(
                 Token::new(TokenType::Eof, "".to_string(), 0),
                 "Unimplemented expression type".to_string(),
@@ -433,7 +675,7 @@ impl Interpreter {
 
         self.environment
             .borrow_mut()
-            .define(name.lexeme.clone(), Literal::Nil);
+            .define(name.symbol, Literal::Nil);
 
         if let Some(sc) = &superclass_val {
             self.environment = Rc::new(RefCell::new(Environment::with_enclosing(
@@ -441,7 +683,7 @@ impl Interpreter {
             )));
             self.environment
                 .borrow_mut()
-                .define("super".to_string(), sc.clone());
+                .define(crate::interner::intern("super"), sc.clone());
         }
 
         let mut class_methods = std::collections::HashMap::new();
@@ -482,13 +724,29 @@ impl Interpreter {
     /// Looks up a variable in the environment.
     fn look_up_variable(&self, name: &Token, expr: &Expr) -> Result<Literal> {
         if let Some(distance) = self.locals.get(&(expr as *const _ as usize)) {
-            self.environment.borrow().get_at(*distance, &name.lexeme)
+            self.environment.borrow().get_at(*distance, name.symbol)
         } else {
             // Fallback for global variables if resolver is not used
             self.environment.borrow().get(name)
         }
     }
 
+    /// Assigns a variable at its resolved scope depth, the assignment
+    /// counterpart to `look_up_variable`. Using the resolved distance
+    /// (rather than walking the environment chain by name) is what makes a
+    /// closure keep writing to the binding it closed over even if an outer
+    /// scope later declares another variable with the same name.
+    fn assign_variable(&mut self, name: &Token, expr: &Expr, value: Literal) -> Result<()> {
+        if let Some(distance) = self.locals.get(&(expr as *const _ as usize)) {
+            self.environment
+                .borrow_mut()
+                .assign_at(*distance, name, value)
+        } else {
+            // Fallback for global variables if resolver is not used
+            self.environment.borrow_mut().assign(name, value)
+        }
+    }
+
     /// Checks if a value is truthy.
     fn is_truthy(&self, value: &Literal) -> bool {
         match value {
@@ -498,16 +756,55 @@ impl Interpreter {
         }
     }
 
-    /// Checks if two values are equal.
-    fn is_equal(&self, a: &Literal, b: &Literal) -> bool {
-        match (a, b) {
+    /// Dispatches an overloadable binary operator (`add`/`sub`/`mul`) to the
+    /// left operand's class, if it defines one; otherwise errors the same
+    /// way the built-in operator does for unsupported operand types.
+    fn call_operator_method(
+        &mut self,
+        left: &Literal,
+        method_name: &str,
+        right: Literal,
+        operator: &Token,
+    ) -> Result<Literal> {
+        if let Literal::Instance(instance) = left {
+            let bound = instance.borrow().find_magic_method(left, method_name);
+            if let Some(method) = bound {
+                return method.call(self, vec![right]);
+            }
+        }
+
+        Err(InterpreterError::Runtime(RuntimeError::new(
+            operator.clone(),
+            "Invalid operands.".to_string(),
+        )))
+    }
+
+    /// Compares two values for `==`/`!=`. When `a` is an instance whose
+    /// class defines an `equals` method, that method decides the result;
+    /// otherwise instances compare by identity and everything else falls
+    /// back to structural equality.
+    fn is_equal(&mut self, a: &Literal, b: &Literal) -> Result<bool> {
+        if let Literal::Instance(instance) = a {
+            let bound = instance.borrow().find_magic_method(a, "equals");
+            if let Some(method) = bound {
+                let result = method.call(self, vec![b.clone()])?;
+                return Ok(self.is_truthy(&result));
+            }
+        }
+
+        Ok(match (a, b) {
             (Literal::Nil, Literal::Nil) => true,
             (Literal::Nil, _) => false,
             (_, Literal::Nil) => false,
             (Literal::Boolean(a), Literal::Boolean(b)) => a == b,
             (Literal::Number(a), Literal::Number(b)) => a == b,
             (Literal::String(a), Literal::String(b)) => a == b,
+            (Literal::Array(_), Literal::Array(_))
+            | (Literal::Map(_), Literal::Map(_))
+            | (Literal::Rational(..), Literal::Rational(..))
+            | (Literal::Complex(..), Literal::Complex(..)) => a.is_equal(b),
+            (Literal::Instance(x), Literal::Instance(y)) => Rc::ptr_eq(x, y),
             _ => false,
-        }
+        })
     }
 }
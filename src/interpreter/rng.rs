@@ -0,0 +1,73 @@
+//! A small, fast, non-cryptographic PRNG (xorshift128+), used by the
+//! stdlib's random-number functions.
+//!
+//! The generator lives on `Interpreter` rather than behind process-global
+//! state so that `seed()` is observable: native functions already receive
+//! `&mut Interpreter`, so threading the RNG through there makes runs
+//! reproducible per-interpreter instead of racing with anything else in
+//! the process that might also want randomness.
+
+/// xorshift128+ generator state.
+pub struct Rng {
+    state: [u64; 2],
+}
+
+impl Rng {
+    /// Seeds the generator from the current time, so unseeded runs still
+    /// vary from one invocation to the next.
+    pub fn new() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        Self::from_seed(seed)
+    }
+
+    /// Seeds the generator deterministically, so the same seed always
+    /// produces the same sequence.
+    pub fn from_seed(seed: u64) -> Self {
+        // xorshift128+ is undefined for an all-zero state, so fold in a
+        // fixed odd constant to keep both words non-zero.
+        let seed = seed ^ 0x9E3779B97F4A7C15;
+        let mut rng = Rng {
+            state: [seed | 1, (seed ^ 0xD1B54A32D192ED03) | 1],
+        };
+        for _ in 0..16 {
+            rng.next_u64();
+        }
+        rng
+    }
+
+    /// Returns the next raw 64-bit output.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut s1 = self.state[0];
+        let s0 = self.state[1];
+        let result = s0.wrapping_add(s1);
+        self.state[0] = s0;
+        s1 ^= s1 << 23;
+        s1 ^= s1 >> 17;
+        s1 ^= s0 ^ (s0 >> 26);
+        self.state[1] = s1;
+        result
+    }
+
+    /// Returns a uniform `f64` in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns a uniform integer in `[lo, hi]`, inclusive on both ends.
+    pub fn next_range(&mut self, lo: i64, hi: i64) -> i64 {
+        if hi <= lo {
+            return lo;
+        }
+        let span = (hi - lo) as u64 + 1;
+        lo + (self.next_u64() % span) as i64
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,287 @@
+//! Converts Demon source text into a flat stream of [`Token`]s.
+
+use crate::lexer::token::{Span, Token, TokenType};
+
+/// Scans a full source string into tokens in one pass.
+///
+/// Construct with [`Scanner::new`] and call [`Scanner::scan_tokens`] once;
+/// the scanner is single-use, mirroring how [`crate::parser::Parser`] is
+/// built fresh from a token slice for each parse.
+#[derive(Debug)]
+pub struct Scanner {
+    source: Vec<char>,
+    tokens: Vec<Token>,
+    start: usize,
+    current: usize,
+    line: usize,
+    col: usize,
+    start_line: usize,
+    start_col: usize,
+    /// Byte offsets into the source matching `start`/`current`, which
+    /// index `source` by char.
+    byte_start: usize,
+    byte_current: usize,
+}
+
+impl Scanner {
+    /// Creates a scanner over `source`.
+    pub fn new(source: String) -> Self {
+        Self {
+            source: source.chars().collect(),
+            tokens: Vec::new(),
+            start: 0,
+            current: 0,
+            line: 1,
+            col: 1,
+            start_line: 1,
+            start_col: 1,
+            byte_start: 0,
+            byte_current: 0,
+        }
+    }
+
+    /// Scans the whole source and returns its tokens, always ending in a
+    /// single [`TokenType::Eof`]. Unrecognized characters and unterminated
+    /// strings are reported to stderr and skipped so the rest of the file
+    /// still scans (mirroring the parser's own resync-and-continue style).
+    pub fn scan_tokens(&mut self) -> Vec<Token> {
+        while !self.is_at_end() {
+            self.start = self.current;
+            self.byte_start = self.byte_current;
+            self.start_line = self.line;
+            self.start_col = self.col;
+            self.scan_token();
+        }
+
+        self.tokens.push(Token::with_span(
+            TokenType::Eof,
+            "".to_string(),
+            self.line,
+            Span::new(self.line, self.col, self.line, self.col, self.byte_current, 0),
+        ));
+        self.tokens.clone()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.current >= self.source.len()
+    }
+
+    fn advance(&mut self) -> char {
+        let c = self.source[self.current];
+        self.current += 1;
+        self.byte_current += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        c
+    }
+
+    fn peek(&self) -> char {
+        self.source.get(self.current).copied().unwrap_or('\0')
+    }
+
+    fn peek_next(&self) -> char {
+        self.source.get(self.current + 1).copied().unwrap_or('\0')
+    }
+
+    fn match_char(&mut self, expected: char) -> bool {
+        if self.is_at_end() || self.source[self.current] != expected {
+            return false;
+        }
+        self.advance();
+        true
+    }
+
+    fn lexeme(&self) -> String {
+        self.source[self.start..self.current].iter().collect()
+    }
+
+    fn span(&self) -> Span {
+        Span::new(
+            self.start_line,
+            self.start_col,
+            self.line,
+            self.col,
+            self.byte_start,
+            self.byte_current - self.byte_start,
+        )
+    }
+
+    fn add_token(&mut self, token_type: TokenType) {
+        let lexeme = self.lexeme();
+        let span = self.span();
+        self.tokens.push(Token::with_span(token_type, lexeme, self.start_line, span));
+    }
+
+    fn error(&self, message: &str) {
+        eprintln!("[line {}] Error: {}", self.start_line, message);
+    }
+
+    fn scan_token(&mut self) {
+        let c = self.advance();
+        match c {
+            '(' => self.add_token(TokenType::LeftParen),
+            ')' => self.add_token(TokenType::RightParen),
+            // `{`/`}` delimit blocks; `[`/`]` delimit array literals and
+            // indexing. The parser treats both spellings as the same
+            // token kind, so the scanner maps both pairs here.
+            '{' | '[' => self.add_token(TokenType::LeftBrace),
+            '}' | ']' => self.add_token(TokenType::RightBrace),
+            ',' => self.add_token(TokenType::Comma),
+            '.' => self.add_token(TokenType::Dot),
+            '-' => {
+                if self.match_char('>') {
+                    self.add_token(TokenType::Arrow);
+                } else {
+                    self.add_token(TokenType::Minus);
+                }
+            }
+            '+' => self.add_token(TokenType::Plus),
+            ';' => self.add_token(TokenType::Semicolon),
+            '*' => self.add_token(TokenType::Star),
+            '&' => self.add_token(TokenType::Ampersand),
+            '!' => {
+                let token_type = if self.match_char('=') { TokenType::BangEqual } else { TokenType::Bang };
+                self.add_token(token_type);
+            }
+            '=' => {
+                let token_type = if self.match_char('=') { TokenType::EqualEqual } else { TokenType::Equal };
+                self.add_token(token_type);
+            }
+            '<' => {
+                let token_type = if self.match_char('=') { TokenType::LessEqual } else { TokenType::Less };
+                self.add_token(token_type);
+            }
+            '>' => {
+                let token_type = if self.match_char('=') { TokenType::GreaterEqual } else { TokenType::Greater };
+                self.add_token(token_type);
+            }
+            '|' => {
+                if self.match_char('>') {
+                    self.add_token(TokenType::Pipe);
+                } else if self.match_char(':') {
+                    self.add_token(TokenType::PipeFold);
+                } else {
+                    self.error("Unexpected character '|'.");
+                }
+            }
+            '/' => {
+                if self.match_char('/') {
+                    while self.peek() != '\n' && !self.is_at_end() {
+                        self.advance();
+                    }
+                } else if self.match_char('*') {
+                    self.block_comment();
+                } else {
+                    self.add_token(TokenType::Slash);
+                }
+            }
+            ' ' | '\r' | '\t' | '\n' => {}
+            '"' => self.string(),
+            _ if c.is_ascii_digit() => self.number(),
+            _ if c.is_alphabetic() || c == '_' => self.identifier(),
+            _ => self.error(&format!("Unexpected character '{}'.", c)),
+        }
+    }
+
+    fn block_comment(&mut self) {
+        let mut depth = 1;
+        while depth > 0 && !self.is_at_end() {
+            if self.peek() == '/' && self.peek_next() == '*' {
+                self.advance();
+                self.advance();
+                depth += 1;
+            } else if self.peek() == '*' && self.peek_next() == '/' {
+                self.advance();
+                self.advance();
+                depth -= 1;
+            } else {
+                self.advance();
+            }
+        }
+        if depth > 0 {
+            self.error("Unterminated block comment.");
+        }
+    }
+
+    fn string(&mut self) {
+        let mut value = String::new();
+        while self.peek() != '"' && !self.is_at_end() {
+            let c = self.advance();
+            if c == '\\' && !self.is_at_end() {
+                value.push(match self.advance() {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '"' => '"',
+                    '\\' => '\\',
+                    '0' => '\0',
+                    other => other,
+                });
+            } else {
+                value.push(c);
+            }
+        }
+
+        if self.is_at_end() {
+            self.error("Unterminated string.");
+            return;
+        }
+
+        // Consume the closing quote.
+        self.advance();
+        self.add_token(TokenType::String(value));
+    }
+
+    fn number(&mut self) {
+        while self.peek().is_ascii_digit() {
+            self.advance();
+        }
+
+        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
+            self.advance();
+            while self.peek().is_ascii_digit() {
+                self.advance();
+            }
+        }
+
+        let value: f64 = self.lexeme().parse().unwrap_or(0.0);
+        self.add_token(TokenType::Number(value));
+    }
+
+    fn identifier(&mut self) {
+        while self.peek().is_alphanumeric() || self.peek() == '_' {
+            self.advance();
+        }
+
+        let text = self.lexeme();
+        let token_type = match text.as_str() {
+            "and" => TokenType::And,
+            "class" => TokenType::Class,
+            "delete" => TokenType::Delete,
+            "else" => TokenType::Else,
+            "false" => TokenType::False,
+            "for" => TokenType::For,
+            "func" => TokenType::Func,
+            "if" => TokenType::If,
+            "nil" => TokenType::Nil,
+            "new" => TokenType::New,
+            "or" => TokenType::Or,
+            "print" => TokenType::Print,
+            "return" => TokenType::Return,
+            "super" => TokenType::Super,
+            "this" => TokenType::This,
+            "true" => TokenType::True,
+            "var" => TokenType::Var,
+            "const" => TokenType::Const,
+            "while" => TokenType::While,
+            "break" => TokenType::Break,
+            "continue" => TokenType::Continue,
+            _ => TokenType::Identifier(text.clone()),
+        };
+        self.add_token(token_type);
+    }
+}
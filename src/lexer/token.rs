@@ -2,6 +2,56 @@
 
 use std::fmt;
 
+use crate::interner::Symbol;
+
+/// A range of source text, used to render precise diagnostics.
+///
+/// Lines and columns are 1-based to match how editors report positions;
+/// `offset`/`len` are 0-based byte offsets into the source for slicing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl Span {
+    /// Creates a new span covering `len` bytes starting at `offset`.
+    pub fn new(
+        start_line: usize,
+        start_col: usize,
+        end_line: usize,
+        end_col: usize,
+        offset: usize,
+        len: usize,
+    ) -> Self {
+        Self {
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+            offset,
+            len,
+        }
+    }
+
+    /// Combines this span with another, producing one that covers both.
+    /// `self` is assumed to start at or before `other`.
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            start_line: self.start_line,
+            start_col: self.start_col,
+            end_line: other.end_line,
+            end_col: other.end_col,
+            offset: self.offset,
+            len: (other.offset + other.len).saturating_sub(self.offset),
+        }
+    }
+}
+
 /// Represents the type of a token in the Demon language.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
@@ -15,7 +65,10 @@ pub enum TokenType {
     Equal, EqualEqual,
     Greater, GreaterEqual,
     Less, LessEqual,
-    
+    Arrow, // ->
+    Pipe,  // |>
+    PipeFold, // |:
+
     // Literals
     Identifier(String),
     String(String),
@@ -24,7 +77,8 @@ pub enum TokenType {
     // Keywords
     And, Class, Delete, Else, False, For, Func, If, Nil, New, Or,
     Print, Return, Super, This, True, Var, Const, While,
-    
+    Break, Continue,
+
     // Special tokens
     Eof,
 }
@@ -35,15 +89,40 @@ pub struct Token {
     pub token_type: TokenType,
     pub lexeme: String,
     pub line: usize,
+    pub span: Span,
+    /// The lexeme, interned once here so downstream lookups (`Environment`)
+    /// can key off a `Copy` `u32` instead of re-hashing `lexeme` on every
+    /// variable access.
+    pub symbol: Symbol,
 }
 
 impl Token {
-    /// Creates a new token.
+    /// Creates a new token without column-accurate span information.
+    ///
+    /// Used for synthetic tokens (desugaring, error placeholders) where no
+    /// real source range exists. Prefer [`Token::with_span`] when scanning
+    /// from source.
     pub fn new(token_type: TokenType, lexeme: String, line: usize) -> Self {
+        let len = lexeme.len();
+        let symbol = crate::interner::intern(&lexeme);
+        Self {
+            token_type,
+            lexeme,
+            line,
+            span: Span::new(line, 0, line, 0, 0, len),
+            symbol,
+        }
+    }
+
+    /// Creates a new token with a precise source span.
+    pub fn with_span(token_type: TokenType, lexeme: String, line: usize, span: Span) -> Self {
+        let symbol = crate::interner::intern(&lexeme);
         Self {
             token_type,
             lexeme,
             line,
+            span,
+            symbol,
         }
     }
 }
@@ -77,6 +156,9 @@ impl fmt::Display for TokenType {
             TokenType::GreaterEqual => write!(f, "GREATER_EQUAL"),
             TokenType::Less => write!(f, "LESS"),
             TokenType::LessEqual => write!(f, "LESS_EQUAL"),
+            TokenType::Arrow => write!(f, "ARROW"),
+            TokenType::Pipe => write!(f, "PIPE"),
+            TokenType::PipeFold => write!(f, "PIPE_FOLD"),
             TokenType::Identifier(s) => write!(f, "IDENTIFIER({})", s),
             TokenType::String(s) => write!(f, "STRING({})", s),
             TokenType::Number(n) => write!(f, "NUMBER({})", n),
@@ -99,6 +181,8 @@ impl fmt::Display for TokenType {
             TokenType::Var => write!(f, "VAR"),
             TokenType::Const => write!(f, "CONST"),
             TokenType::While => write!(f, "WHILE"),
+            TokenType::Break => write!(f, "BREAK"),
+            TokenType::Continue => write!(f, "CONTINUE"),
             TokenType::Eof => write!(f, "EOF"),
         }
     }
@@ -2,18 +2,23 @@
 //! This library provides the lexer, parser, and interpreter for the Demon language.
 
 pub mod error;
+pub mod interner;
 pub mod lexer;
 pub mod parser;
 pub mod interpreter;
 pub mod memory;
+pub mod resolver;
 pub mod stdlib;
+pub mod vm;
 
 // Re-exports for common types
 pub use error::{Result, Error, ParseError, RuntimeError, InterpreterError};
 pub use interpreter::Interpreter;
-pub use lexer::{Scanner, Token, TokenType};
+pub use lexer::{Scanner, Span, Token, TokenType};
 pub use parser::{Parser, Stmt, Expr};
-pub use memory::{RawPointer, SharedPointer, Allocator, GlobalAllocator};
+pub use memory::{RawPointer, SharedPointer, Allocator, ArenaAllocator, GlobalAllocator};
+pub use resolver::Locals;
+pub use interner::{Interner, Symbol};
 
 // Re-export Literal from the parser module's public interface
 pub use parser::Literal;
@@ -28,17 +33,32 @@ pub fn new_interpreter() -> Interpreter {
     interpreter
 }
 
-/// Parses a string of Demon code into a vector of statements
-pub fn parse(source: &str) -> Result<Vec<Stmt>> {
+/// Parses a string of Demon code into a vector of statements.
+///
+/// On a parse error this carries every error found in the source (the
+/// parser resynchronizes after each one rather than stopping at the
+/// first), not just one.
+pub fn parse(source: &str) -> std::result::Result<Vec<Stmt>, Vec<Error>> {
     let mut scanner = Scanner::new(source.to_string());
     let tokens = scanner.scan_tokens();
     let mut parser = Parser::new(&tokens);
     parser.parse()
 }
 
-/// Executes a string of Demon code
+/// Executes a string of Demon code.
+///
+/// If parsing fails, every collected parse error is printed to stderr and
+/// the first one is returned (callers that only care about success/failure
+/// don't need to handle a batch of errors themselves).
 pub fn execute(source: &str) -> Result<()> {
-    let stmts = parse(source)?;
+    let stmts = parse(source).map_err(|errors| {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        errors.into_iter().next().expect("parse errors are never empty on Err")
+    })?;
+    let locals = resolver::resolve(&stmts)?;
     let mut interpreter = new_interpreter();
+    interpreter.load_resolution(locals);
     interpreter.interpret(&stmts)
 }
@@ -1,10 +1,18 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use demon::{self, new_interpreter, Interpreter};
 use log::info;
 use std::error::Error;
 use std::fs;
 use std::path::PathBuf;
 
+/// Which execution backend runs a script: the tree-walking `Interpreter`,
+/// or the bytecode `vm::Vm`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Backend {
+    Tree,
+    Vm,
+}
+
 /// Demon Programming Language Interpreter
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -15,6 +23,28 @@ struct Args {
     /// Enables debug mode
     #[clap(short, long)]
     debug: bool,
+
+    /// Execution backend to use
+    #[clap(long, value_enum, default_value_t = Backend::Tree)]
+    backend: Backend,
+}
+
+/// The execution backend for one run of the REPL or a script file. Kept
+/// alive across REPL lines the same way `Interpreter` already was, so
+/// top-level `var`/`func` declarations persist between prompts on either
+/// backend.
+enum Runtime {
+    Tree(Interpreter),
+    Vm(demon::vm::Vm),
+}
+
+impl Runtime {
+    fn new(backend: Backend) -> Self {
+        match backend {
+            Backend::Tree => Runtime::Tree(new_interpreter()),
+            Backend::Vm => Runtime::Vm(demon::vm::Vm::new()),
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -27,29 +57,29 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
     pretty_env_logger::init();
 
-    let mut interpreter = new_interpreter();
+    let mut runtime = Runtime::new(args.backend);
 
     if let Some(script_path) = args.script {
-        run_file(script_path.to_str().unwrap(), &mut interpreter)?;
+        run_file(script_path.to_str().unwrap(), &mut runtime)?;
     } else {
-        run_prompt(&mut interpreter)?;
+        run_prompt(&mut runtime)?;
     }
     Ok(())
 }
 
 /// Runs the Demon script from a file
-fn run_file(path: &str, interpreter: &mut Interpreter) -> Result<(), Box<dyn Error>> {
+fn run_file(path: &str, runtime: &mut Runtime) -> Result<(), Box<dyn Error>> {
     let source = fs::read_to_string(path)?;
-    run(interpreter, &source)?;
+    run(runtime, &source)?;
     Ok(())
 }
 
 /// Starts the Demon REPL (Read-Eval-Print Loop)
-fn run_prompt(interpreter: &mut Interpreter) -> Result<(), Box<dyn Error>> {
+fn run_prompt(runtime: &mut Runtime) -> Result<(), Box<dyn Error>> {
     let mut rl = rustyline::DefaultEditor::new()?;
     info!("Demon REPL (Ctrl+C to exit)");
     println!("Type 'exit' or press Ctrl+C to quit.\n");
-    
+
     loop {
         let readline = rl.readline("demon> ");
         match readline {
@@ -57,13 +87,13 @@ fn run_prompt(interpreter: &mut Interpreter) -> Result<(), Box<dyn Error>> {
                 if line.trim().eq_ignore_ascii_case("exit") {
                     break;
                 }
-                
+
                 if !line.trim().is_empty() {
-                    if let Err(e) = run(interpreter, &line) {
+                    if let Err(e) = run(runtime, &line) {
                         eprintln!("Error: {}", e);
                     }
                 }
-                
+
                 // Add to history
                 let _ = rl.add_history_entry(line);
             }
@@ -73,12 +103,12 @@ fn run_prompt(interpreter: &mut Interpreter) -> Result<(), Box<dyn Error>> {
             }
         }
     }
-    
+
     Ok(())
 }
 
 /// Runs the Demon source code
-fn run(interpreter: &mut Interpreter, source: &str) -> Result<(), Box<dyn Error>> {
+fn run(runtime: &mut Runtime, source: &str) -> Result<(), Box<dyn Error>> {
     if cfg!(debug_assertions) {
         println!("=== Source Code ===");
         println!("{}", source);
@@ -98,18 +128,46 @@ fn run(interpreter: &mut Interpreter, source: &str) -> Result<(), Box<dyn Error>
     let mut parser = demon::parser::Parser::new(&tokens);
     let statements = match parser.parse() {
         Ok(s) => s,
-        Err(e) => {
-            eprintln!("{}", e);
+        Err(errors) => {
+            // Report every error found in the source, not just the first,
+            // now that the parser resynchronizes and keeps going instead of
+            // bailing out at the first mistake.
+            for error in &errors {
+                eprintln!("{}", error.report(source));
+            }
             return Ok(()); // Continue the REPL even if there's a parse error
         }
     };
-    
+
     if cfg!(debug_assertions) {
         for stmt in &statements {
             println!("AST: {}", stmt);
         }
     }
-    
-    interpreter.interpret(&statements)?;
+
+    match runtime {
+        Runtime::Tree(interpreter) => {
+            let locals = demon::resolver::resolve(&statements)?;
+            interpreter.load_resolution(locals);
+
+            if let Err(error) = interpreter.interpret(&statements) {
+                eprintln!("{}", error.report(source));
+                return Ok(());
+            }
+        }
+        Runtime::Vm(vm) => {
+            let script = match demon::vm::compile(&statements) {
+                Ok(script) => script,
+                Err(error) => {
+                    eprintln!("{}", error.report(source));
+                    return Ok(());
+                }
+            };
+            if let Err(error) = vm.run(script) {
+                eprintln!("{}", error.report(source));
+                return Ok(());
+            }
+        }
+    }
     Ok(())
 }
@@ -2,8 +2,9 @@
 //! Provides C++-like memory management features including raw pointers and custom allocators.
 
 use std::alloc::{alloc, dealloc, Layout};
+use std::cell::{Cell, RefCell};
+use std::fmt;
 use std::ptr;
-use std::cell::RefCell;
 use std::rc::Rc;
 
 /// A raw pointer type for Demon language
@@ -85,18 +86,28 @@ impl<T> SharedPointer<T> {
 }
 
 /// Custom allocator trait
-pub trait Allocator {
+pub trait Allocator: fmt::Debug {
+    /// Supports downcasting to a concrete allocator type (e.g. so
+    /// `arena_reset` can recover the `ArenaAllocator` behind a
+    /// `Literal::Allocator`), mirroring `Callable::as_any`.
+    fn as_any(&self) -> &dyn std::any::Any;
+
     /// Allocates memory with the given layout
     unsafe fn allocate(&self, layout: Layout) -> *mut u8;
-    
+
     /// Deallocates memory previously allocated with this allocator
     unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
 }
 
 /// The default global allocator
+#[derive(Debug)]
 pub struct GlobalAllocator;
 
 impl Allocator for GlobalAllocator {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
     unsafe fn allocate(&self, layout: Layout) -> *mut u8 {
         alloc(layout)
     }
@@ -106,6 +117,118 @@ impl Allocator for GlobalAllocator {
     }
 }
 
+/// The chunk size a fresh `ArenaAllocator` starts with, in bytes.
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// A bump (region) allocator: `allocate` just advances an offset into the
+/// current chunk, and `deallocate` is a no-op, since nothing is freed
+/// individually — the whole region is reclaimed at once via `reset`. This
+/// trades per-object `free` for much cheaper allocation, which suits
+/// scoped/temporary object graphs that all die together.
+#[derive(Debug)]
+pub struct ArenaAllocator {
+    /// Chunks allocated so far. Each chunk is reserved at its full capacity
+    /// up front and never resized afterwards, so pointers handed out into
+    /// it stay valid for the chunk's lifetime (a `Vec` growing in place
+    /// would invalidate them).
+    chunks: RefCell<Vec<Vec<u8>>>,
+    /// Byte offset of the next free slot within the current (last) chunk.
+    offset: Cell<usize>,
+    /// Size used for each new chunk, unless a single allocation is bigger.
+    chunk_size: usize,
+}
+
+impl ArenaAllocator {
+    /// Creates an arena that grows in `DEFAULT_CHUNK_SIZE`-byte chunks.
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates an arena that grows in `chunk_size`-byte chunks.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunks: RefCell::new(vec![Vec::with_capacity(chunk_size)]),
+            offset: Cell::new(0),
+            chunk_size,
+        }
+    }
+
+    /// Returns the total number of chunks allocated so far. Mainly useful
+    /// for tests asserting that growth actually happens.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+
+    /// Returns the bump offset into the current (last) chunk, i.e. how many
+    /// bytes have been charged to that chunk. The count restarts at 0 when
+    /// `allocate` moves on to a new chunk, so it is not a total across
+    /// chunks. Mainly useful for tests asserting that `allocate` calls
+    /// actually charge against the arena.
+    pub fn bytes_used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Frees every allocation made through this arena at once by dropping
+    /// all chunks and starting fresh. Anything previously allocated here
+    /// becomes a dangling pointer the moment this is called; it's the
+    /// caller's responsibility not to touch it afterwards.
+    pub fn reset(&self) {
+        let mut chunks = self.chunks.borrow_mut();
+        chunks.clear();
+        chunks.push(Vec::with_capacity(self.chunk_size));
+        self.offset.set(0);
+    }
+}
+
+impl Default for ArenaAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Allocator for ArenaAllocator {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    unsafe fn allocate(&self, layout: Layout) -> *mut u8 {
+        let mut chunks = self.chunks.borrow_mut();
+
+        let fits_current_chunk = {
+            let current = chunks.last().expect("arena always has at least one chunk");
+            let base = current.as_ptr() as usize;
+            let aligned = align_up(base + self.offset.get(), layout.align());
+            aligned + layout.size() <= base + current.capacity()
+        };
+
+        if !fits_current_chunk {
+            // Doesn't fit what's left of the current chunk: start a new
+            // one, sized to fit at least this request even if that's
+            // bigger than our usual chunk size.
+            let new_size = self.chunk_size.max(layout.size() + layout.align());
+            chunks.push(Vec::with_capacity(new_size));
+            self.offset.set(0);
+        }
+
+        let current = chunks.last().expect("arena always has at least one chunk");
+        let base = current.as_ptr() as usize;
+        let aligned = align_up(base + self.offset.get(), layout.align());
+        self.offset.set(aligned - base + layout.size());
+        aligned as *mut u8
+    }
+
+    unsafe fn deallocate(&self, _ptr: *mut u8, _layout: Layout) {
+        // No-op by design: individual allocations are never freed, only
+        // the whole region at once via `reset`.
+    }
+}
+
+/// Rounds `addr` up to the nearest multiple of `align` (which must be a
+/// power of two, as `Layout::align` guarantees).
+fn align_up(addr: usize, align: usize) -> usize {
+    (addr + align - 1) & !(align - 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +248,57 @@ mod tests {
         ptr.borrow_mut().push_str("ing");
         assert_eq!(*ptr.borrow(), "testing");
     }
+
+    #[test]
+    fn test_arena_allocator_alignment() {
+        let arena = ArenaAllocator::new();
+        unsafe {
+            // A byte, then something with an 8-byte alignment requirement:
+            // the second allocation must come back aligned even though the
+            // first left the offset unaligned.
+            let _byte = arena.allocate(Layout::new::<u8>());
+            let aligned = arena.allocate(Layout::new::<u64>());
+            assert_eq!(aligned as usize % std::mem::align_of::<u64>(), 0);
+        }
+    }
+
+    #[test]
+    fn test_arena_allocator_grows_new_chunk_when_exhausted() {
+        let arena = ArenaAllocator::with_chunk_size(64);
+        assert_eq!(arena.chunk_count(), 1);
+
+        unsafe {
+            // Fill up (most of) the first chunk.
+            let layout = Layout::array::<u8>(32).unwrap();
+            arena.allocate(layout);
+            arena.allocate(layout);
+            assert_eq!(arena.chunk_count(), 1);
+
+            // This no longer fits in what's left of the first chunk, so a
+            // new one must be started.
+            arena.allocate(layout);
+            assert_eq!(arena.chunk_count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_arena_allocator_reset_reclaims_everything() {
+        let arena = ArenaAllocator::with_chunk_size(64);
+        unsafe {
+            let layout = Layout::array::<u8>(32).unwrap();
+            arena.allocate(layout);
+            arena.allocate(layout);
+            arena.allocate(layout); // forces a second chunk
+        }
+        assert_eq!(arena.chunk_count(), 2);
+
+        arena.reset();
+        assert_eq!(arena.chunk_count(), 1);
+
+        // The arena is usable again after reset, starting from offset 0.
+        unsafe {
+            let ptr = arena.allocate(Layout::new::<u64>());
+            assert_eq!(ptr as usize % std::mem::align_of::<u64>(), 0);
+        }
+    }
 }
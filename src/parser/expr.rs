@@ -1,14 +1,17 @@
 //! Expression nodes for the Demon language AST.
 
 use std::fmt;
+use std::hash::Hash;
 use std::rc::Rc;
 use std::cell::RefCell;
 use crate::error::general_error;
 use std::collections::HashMap;
 
-use crate::interpreter::{Callable, Class, Instance};
-use crate::lexer::Token;
+use crate::interpreter::{Callable, Class, Instance, LazyIter};
+use crate::lexer::{Span, Token};
 use crate::error::Result;
+use crate::memory::Allocator;
+use crate::parser::stmt::Stmt;
 
 /// Represents an expression in the Demon language.
 #[derive(Debug, Clone)]
@@ -87,7 +90,11 @@ pub enum Expr {
         index: Box<Expr>,
     },
     
-    /// Custom allocator expression (e.g., new(allocator) MyClass())
+    /// Custom allocator expression (e.g., `new(allocator) MyClass()`). The
+    /// instance itself is still an ordinary `Rc<RefCell<Instance>>`; what
+    /// this charges to `allocator` is its footprint in bytes, so a
+    /// `reset()` on an arena bounds how much budget repeated `new(arena)`
+    /// calls consume without actually relocating the object there.
     CustomNew {
         allocator: Box<Expr>,
         class: Box<Expr>,
@@ -98,6 +105,16 @@ pub enum Expr {
         name: Token,
         value: Box<Expr>,
     },
+
+    /// An index assignment (e.g., `object[index] = value`), the `[]`
+    /// counterpart to `Set`. Resolved at runtime through the object's `set`
+    /// magic method rather than a built-in slot, since `Expr::ArrayAccess`
+    /// only supports index access on instances that define `get`/`set`.
+    IndexSet {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        value: Box<Expr>,
+    },
     
     /// A logical operation (e.g., true or false)
     Logical {
@@ -120,6 +137,26 @@ pub enum Expr {
         keyword: Token,
         method: Token,
     },
+
+    /// An anonymous function (e.g., `x -> x + 1` or `(a, b) -> { return a + b; }`)
+    ///
+    /// `body` is `Rc`-shared, same as `Stmt::Function::body` and for the
+    /// same reason: evaluating the lambda clones the `Rc` handle rather than
+    /// the tree, so node addresses stay stable between `resolver::resolve`
+    /// and interpretation.
+    Lambda {
+        params: Vec<Token>,
+        body: Rc<Vec<Stmt>>,
+    },
+
+    /// A pipeline expression. `x |> f` is sugar for `f(x)`; `x |: f(a)` is
+    /// "fold" sugar that appends `x` as a trailing argument, `f(a, x)`,
+    /// rather than calling `f` with `x` alone.
+    Pipeline {
+        value: Box<Expr>,
+        func: Box<Expr>,
+        fold: bool,
+    },
 }
 
 /// Represents a literal value in the AST.
@@ -151,6 +188,24 @@ pub enum Literal {
     
     /// A key-value map
     Map(Rc<RefCell<HashMap<String, Literal>>>),
+
+    /// An allocator value (e.g. from `new(allocator) Type()`), such as the
+    /// one `arena()` in the standard library produces.
+    Allocator(Rc<dyn Allocator>),
+
+    /// A lazy, pull-based sequence produced by `range`/`iter`/a combinator
+    /// like `filter_iter`/`map_iter`/`take`/`zip`/`enumerate`. Nothing is
+    /// computed until a terminal operation (`collect`/`reduce`/`fold`/
+    /// `for_each`) pulls values from it.
+    Iterator(LazyIter),
+
+    /// An exact rational number, always stored in lowest terms with the
+    /// sign folded into the numerator and the denominator positive (so
+    /// `Rational(1, 2) == Rational(1, 2)` is plain structural equality).
+    Rational(i64, i64),
+
+    /// A complex number (real, imaginary).
+    Complex(f64, f64),
 }
 
 impl Expr {
@@ -167,6 +222,7 @@ impl Expr {
             Expr::Call { callee, .. } => callee.first_token(),
             Expr::Get { object, .. } => object.first_token(),
             Expr::Set { object, .. } => object.first_token(),
+            Expr::IndexSet { object, .. } => object.first_token(),
             Expr::This(keyword) => keyword.clone(),
             Expr::Super { keyword, .. } => keyword.clone(),
             Expr::New { class, .. } => class.first_token(),
@@ -177,8 +233,62 @@ impl Expr {
             Expr::DeleteArray { target } => target.first_token(),
             Expr::ArrayAccess { array, .. } => array.first_token(),
             Expr::CustomNew { allocator, .. } => allocator.first_token(),
+            Expr::Lambda { params, body } => params
+                .first()
+                .cloned()
+                .or_else(|| body.first().and_then(Stmt::first_token))
+                .unwrap_or_else(|| Token::new(crate::lexer::TokenType::Nil, String::new(), 0)),
+            Expr::Pipeline { value, .. } => value.first_token(),
         }
     }
+
+    /// Returns the last token of the expression for error reporting and spans.
+    pub fn last_token(&self) -> Token {
+        match self {
+            Expr::Literal(token) => token.clone(),
+            Expr::Variable(token) => token.clone(),
+            Expr::Unary { right, .. } => right.last_token(),
+            Expr::Binary { right, .. } => right.last_token(),
+            Expr::Logical { right, .. } => right.last_token(),
+            Expr::Assign { value, .. } => value.last_token(),
+            Expr::Grouping(expr) => expr.last_token(),
+            Expr::Call { callee, arguments } => arguments
+                .last()
+                .map(Expr::last_token)
+                .unwrap_or_else(|| callee.last_token()),
+            Expr::Get { name, .. } => name.clone(),
+            Expr::Set { value, .. } => value.last_token(),
+            Expr::IndexSet { value, .. } => value.last_token(),
+            Expr::This(keyword) => keyword.clone(),
+            Expr::Super { method, .. } => method.clone(),
+            Expr::New { class, arguments } => arguments
+                .last()
+                .map(Expr::last_token)
+                .unwrap_or_else(|| class.last_token()),
+            Expr::Delete { target } => target.last_token(),
+            Expr::Dereference { expression } => expression.last_token(),
+            Expr::AddressOf { expression } => expression.last_token(),
+            Expr::NewArray { size, .. } => size.last_token(),
+            Expr::DeleteArray { target } => target.last_token(),
+            Expr::ArrayAccess { index, .. } => index.last_token(),
+            Expr::CustomNew { class, arguments, .. } => arguments
+                .last()
+                .map(Expr::last_token)
+                .unwrap_or_else(|| class.last_token()),
+            Expr::Lambda { params, body } => body
+                .last()
+                .and_then(Stmt::last_token)
+                .or_else(|| params.last().cloned())
+                .unwrap_or_else(|| Token::new(crate::lexer::TokenType::Nil, String::new(), 0)),
+            Expr::Pipeline { func, .. } => func.last_token(),
+        }
+    }
+
+    /// Returns the full source span of the expression, from its first token
+    /// to its last.
+    pub fn span(&self) -> Span {
+        self.first_token().span.to(&self.last_token().span)
+    }
 }
 
 impl fmt::Display for Expr {
@@ -201,6 +311,7 @@ impl fmt::Display for Expr {
             }
             Expr::Get { object, name } => write!(f, "{}.{}", object, name.lexeme),
             Expr::Set { object, name, value } => write!(f, "{}.{} = {}", object, name.lexeme, value),
+            Expr::IndexSet { object, index, value } => write!(f, "{}[{}] = {}", object, index, value),
             Expr::Logical {
                 left,
                 operator,
@@ -225,6 +336,14 @@ impl fmt::Display for Expr {
                 let args: Vec<String> = arguments.iter().map(ToString::to_string).collect();
                 write!(f, "new({}) {}({})", allocator, class, args.join(", "))
             }
+            Expr::Lambda { params, body } => {
+                let param_list: Vec<String> = params.iter().map(|p| p.lexeme.clone()).collect();
+                write!(f, "({}) -> {}", param_list.join(", "), Stmt::Block((**body).clone()))
+            }
+            Expr::Pipeline { value, func, fold } => {
+                let op = if *fold { "|:" } else { "|>" };
+                write!(f, "{} {} {}", value, op, func)
+            }
         }
     }
 }
@@ -251,18 +370,90 @@ impl fmt::Display for Literal {
                     .collect();
                 write!(f, "{{{}}}", pairs.join(", "))
             },
+            Literal::Allocator(_) => write!(f, "<allocator>"),
+            Literal::Iterator(_) => write!(f, "<iterator>"),
+            Literal::Rational(num, den) => write!(f, "{}/{}", num, den),
+            Literal::Complex(re, im) => {
+                if *im < 0.0 {
+                    write!(f, "{}-{}i", re, -im)
+                } else {
+                    write!(f, "{}+{}i", re, im)
+                }
+            }
         }
     }
 }
 
 impl PartialEq for Literal {
+    /// Delegates to `is_equal`, which already implements recursive
+    /// structural equality for `Array`/`Map` (depth-bounded, with an
+    /// `Rc::ptr_eq` fast path for self-referential maps).
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Literal::Number(a), Literal::Number(b)) => a == b,
-            (Literal::String(a), Literal::String(b)) => a == b,
-            (Literal::Boolean(a), Literal::Boolean(b)) => a == b,
-            (Literal::Nil, Literal::Nil) => true,
-            _ => false,
+        self.is_equal(other)
+    }
+}
+
+// NaN makes this not fully reflexive for `Literal::Number`, same caveat as
+// most scripting languages accept for float equality; `Eq` is only needed
+// here so `Literal` can be hashed for use as e.g. array/map elements.
+impl Eq for Literal {}
+
+impl std::hash::Hash for Literal {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash_at_depth(state, 0);
+    }
+}
+
+impl Literal {
+    fn hash_at_depth<H: std::hash::Hasher>(&self, state: &mut H, depth: usize) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Literal::Number(n) => n.to_bits().hash(state),
+            Literal::String(s) => s.hash(state),
+            Literal::Boolean(b) => b.hash(state),
+            Literal::Nil => {}
+            Literal::Rational(num, den) => {
+                num.hash(state);
+                den.hash(state);
+            }
+            Literal::Complex(re, im) => {
+                re.to_bits().hash(state);
+                im.to_bits().hash(state);
+            }
+            Literal::Array(elements) => {
+                // Beyond the depth bound, stop recursing (matching
+                // `is_equal_at_depth`) rather than risk a stack overflow on
+                // a self-referential array; the discriminant hashed above
+                // is still a valid, if coarser, hash.
+                if depth < MAX_STRUCTURAL_DEPTH {
+                    for element in elements {
+                        element.hash_at_depth(state, depth + 1);
+                    }
+                }
+            }
+            Literal::Map(map) => {
+                if depth < MAX_STRUCTURAL_DEPTH {
+                    // Hash a key-sorted view so structurally equal maps
+                    // (whose entries may be stored in different orders)
+                    // hash the same.
+                    let map = map.borrow();
+                    let mut entries: Vec<(&String, &Literal)> = map.iter().collect();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                    for (key, value) in entries {
+                        key.hash(state);
+                        value.hash_at_depth(state, depth + 1);
+                    }
+                }
+            }
+            // These are never equal to anything (see `PartialEq` above), so
+            // hashing just the discriminant can't violate the Hash/Eq
+            // contract; collisions between distinct callables/instances are
+            // safe for a Hasher to produce.
+            Literal::Callable(_)
+            | Literal::Instance(_)
+            | Literal::Class(_)
+            | Literal::Allocator(_)
+            | Literal::Iterator(_) => {}
         }
     }
 }
@@ -273,11 +464,156 @@ impl std::ops::Neg for Literal {
     fn neg(self) -> Self::Output {
         match self {
             Literal::Number(n) => Ok(Literal::Number(-n)),
+            Literal::Rational(num, den) => Ok(Literal::Rational(-num, den)),
+            Literal::Complex(re, im) => Ok(Literal::Complex(-re, -im)),
             _ => Err(general_error("Operand must be a number.")),
         }
     }
 }
 
+/// Largest common divisor of two non-negative integers, used by
+/// `Literal::rational` to reduce a fraction to lowest terms.
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Literal {
+    /// Builds a `Rational` in lowest terms, with the sign folded into the
+    /// numerator and the denominator positive. Errors on a zero
+    /// denominator the same way dividing by zero does elsewhere.
+    pub fn rational(numerator: i64, denominator: i64) -> Result<Literal> {
+        if denominator == 0 {
+            return Err(general_error("Rational denominator must not be zero."));
+        }
+        let sign = if denominator < 0 { -1 } else { 1 };
+        let (numerator, denominator) = (numerator * sign, denominator * sign);
+        let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs()).max(1) as i64;
+        Ok(Literal::Rational(numerator / divisor, denominator / divisor))
+    }
+
+    /// True for the numeric-tower variants (`Rational`, `Number`,
+    /// `Complex`) that arithmetic and the math stdlib promote between.
+    pub fn is_numeric_tower(&self) -> bool {
+        matches!(self, Literal::Rational(..) | Literal::Number(_) | Literal::Complex(..))
+    }
+
+    /// Approximates a numeric-tower value (other than `Complex`, which has
+    /// no single real value) as an `f64`.
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Literal::Number(n) => Some(*n),
+            Literal::Rational(num, den) => Some(*num as f64 / *den as f64),
+            _ => None,
+        }
+    }
+
+    /// Widens any numeric-tower value to a `(real, imaginary)` pair, the
+    /// representation `Complex` arithmetic operates on.
+    fn as_complex_pair(&self) -> Option<(f64, f64)> {
+        match self {
+            Literal::Complex(re, im) => Some((*re, *im)),
+            _ => self.as_f64().map(|n| (n, 0.0)),
+        }
+    }
+
+    /// Adds two numeric-tower values, promoting along `Rational -> Number
+    /// -> Complex` to the loosest representation either operand needs.
+    pub fn numeric_add(&self, other: &Literal) -> Result<Literal> {
+        match (self, other) {
+            (Literal::Rational(n1, d1), Literal::Rational(n2, d2)) => {
+                Literal::rational(n1 * d2 + n2 * d1, d1 * d2)
+            }
+            (Literal::Complex(..), _) | (_, Literal::Complex(..)) => {
+                let (r1, i1) = self.complex_operand()?;
+                let (r2, i2) = other.complex_operand()?;
+                Ok(Literal::Complex(r1 + r2, i1 + i2))
+            }
+            _ => Ok(Literal::Number(self.real_operand()? + other.real_operand()?)),
+        }
+    }
+
+    /// Subtracts two numeric-tower values with the same promotion rule as
+    /// `numeric_add`.
+    pub fn numeric_sub(&self, other: &Literal) -> Result<Literal> {
+        match (self, other) {
+            (Literal::Rational(n1, d1), Literal::Rational(n2, d2)) => {
+                Literal::rational(n1 * d2 - n2 * d1, d1 * d2)
+            }
+            (Literal::Complex(..), _) | (_, Literal::Complex(..)) => {
+                let (r1, i1) = self.complex_operand()?;
+                let (r2, i2) = other.complex_operand()?;
+                Ok(Literal::Complex(r1 - r2, i1 - i2))
+            }
+            _ => Ok(Literal::Number(self.real_operand()? - other.real_operand()?)),
+        }
+    }
+
+    /// Multiplies two numeric-tower values with the same promotion rule as
+    /// `numeric_add`.
+    pub fn numeric_mul(&self, other: &Literal) -> Result<Literal> {
+        match (self, other) {
+            (Literal::Rational(n1, d1), Literal::Rational(n2, d2)) => {
+                Literal::rational(n1 * n2, d1 * d2)
+            }
+            (Literal::Complex(..), _) | (_, Literal::Complex(..)) => {
+                let (r1, i1) = self.complex_operand()?;
+                let (r2, i2) = other.complex_operand()?;
+                Ok(Literal::Complex(r1 * r2 - i1 * i2, r1 * i2 + i1 * r2))
+            }
+            _ => Ok(Literal::Number(self.real_operand()? * other.real_operand()?)),
+        }
+    }
+
+    /// Divides two numeric-tower values with the same promotion rule as
+    /// `numeric_add`. Errors on division by zero at every rung of the
+    /// tower (a zero rational numerator, a zero real, or a zero-modulus
+    /// complex divisor).
+    pub fn numeric_div(&self, other: &Literal) -> Result<Literal> {
+        match (self, other) {
+            (Literal::Rational(n1, d1), Literal::Rational(n2, d2)) => {
+                if *n2 == 0 {
+                    return Err(general_error("Division by zero."));
+                }
+                Literal::rational(n1 * d2, d1 * n2)
+            }
+            (Literal::Complex(..), _) | (_, Literal::Complex(..)) => {
+                let (r1, i1) = self.complex_operand()?;
+                let (r2, i2) = other.complex_operand()?;
+                let denom = r2 * r2 + i2 * i2;
+                if denom == 0.0 {
+                    return Err(general_error("Division by zero."));
+                }
+                Ok(Literal::Complex(
+                    (r1 * r2 + i1 * i2) / denom,
+                    (i1 * r2 - r1 * i2) / denom,
+                ))
+            }
+            _ => {
+                let (a, b) = (self.real_operand()?, other.real_operand()?);
+                if b == 0.0 {
+                    return Err(general_error("Division by zero."));
+                }
+                Ok(Literal::Number(a / b))
+            }
+        }
+    }
+
+    /// `as_f64`, but with the "operands must be numbers" error this
+    /// module's arithmetic reports for a non-numeric operand.
+    fn real_operand(&self) -> Result<f64> {
+        self.as_f64().ok_or_else(|| general_error("Operands must be numbers."))
+    }
+
+    /// `as_complex_pair`, but with the "operands must be numbers" error.
+    fn complex_operand(&self) -> Result<(f64, f64)> {
+        self.as_complex_pair().ok_or_else(|| general_error("Operands must be numbers."))
+    }
+}
+
 impl std::ops::Not for Literal {
     type Output = bool;
 
@@ -286,6 +622,13 @@ impl std::ops::Not for Literal {
     }
 }
 
+/// Maximum nesting depth walked when structurally comparing or hashing
+/// `Array`/`Map` literals. Bounds recursion so a self-referential map (one
+/// that holds a reference to itself) can't blow the stack; beyond this
+/// depth nested collections are treated as unequal rather than recursed
+/// into further.
+const MAX_STRUCTURAL_DEPTH: usize = 64;
+
 impl Literal {
     /// Returns true if the value is truthy.
     pub fn is_truthy(&self) -> bool {
@@ -297,7 +640,14 @@ impl Literal {
     }
 
     /// Checks if two values are equal.
+    ///
+    /// `Array`s and `Map`s compare structurally (element-wise / key-wise),
+    /// recursing into nested collections up to `MAX_STRUCTURAL_DEPTH`.
     pub fn is_equal(&self, other: &Self) -> bool {
+        self.is_equal_at_depth(other, 0)
+    }
+
+    fn is_equal_at_depth(&self, other: &Self, depth: usize) -> bool {
         match (self, other) {
             (Literal::Nil, Literal::Nil) => true,
             (Literal::Nil, _) => false,
@@ -305,7 +655,66 @@ impl Literal {
             (Literal::Boolean(a), Literal::Boolean(b)) => a == b,
             (Literal::Number(a), Literal::Number(b)) => a == b,
             (Literal::String(a), Literal::String(b)) => a == b,
+            (Literal::Rational(n1, d1), Literal::Rational(n2, d2)) => n1 == n2 && d1 == d2,
+            (Literal::Complex(r1, i1), Literal::Complex(r2, i2)) => r1 == r2 && i1 == i2,
+            (Literal::Array(a), Literal::Array(b)) => {
+                if depth >= MAX_STRUCTURAL_DEPTH {
+                    return false;
+                }
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(x, y)| x.is_equal_at_depth(y, depth + 1))
+            }
+            (Literal::Map(a), Literal::Map(b)) => {
+                // Fast path, and the only way to terminate on a map that
+                // refers to itself: identical `Rc`s are trivially equal
+                // without ever borrowing/recursing into their contents.
+                if Rc::ptr_eq(a, b) {
+                    return true;
+                }
+                if depth >= MAX_STRUCTURAL_DEPTH {
+                    return false;
+                }
+                let a = a.borrow();
+                let b = b.borrow();
+                a.len() == b.len()
+                    && a.iter().all(|(key, value)| {
+                        b.get(key)
+                            .is_some_and(|other| value.is_equal_at_depth(other, depth + 1))
+                    })
+            }
             _ => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_div_rational_by_zero_numerator_errors() {
+        let a = Literal::rational(1, 2).unwrap();
+        let b = Literal::rational(0, 5).unwrap();
+        assert!(a.numeric_div(&b).is_err());
+    }
+
+    #[test]
+    fn test_numeric_div_real_by_zero_errors() {
+        let result = Literal::Number(1.0).numeric_div(&Literal::Number(0.0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_numeric_div_complex_by_zero_errors() {
+        let result = Literal::Complex(1.0, 1.0).numeric_div(&Literal::Complex(0.0, 0.0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_numeric_div_ok() {
+        let result = Literal::Number(6.0).numeric_div(&Literal::Number(2.0)).unwrap();
+        assert_eq!(result, Literal::Number(3.0));
+    }
+}
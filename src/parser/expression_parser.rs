@@ -2,11 +2,160 @@
 
 use super::*;
 use crate::lexer::TokenType::*;
+use std::rc::Rc;
 
 impl<'a> Parser<'a> {
     /// Parses an expression.
     pub fn expression(&mut self) -> Result<Expr> {
-        self.assignment()
+        self.lambda()
+    }
+
+    /// Parses a lambda expression (`x -> expr` or `(a, b) -> { ... }`), the
+    /// loosest-binding form. Falls through to `pipeline` when the upcoming
+    /// tokens don't form an arrow-function header.
+    fn lambda(&mut self) -> Result<Expr> {
+        if let Some(params) = self.try_match_lambda_params() {
+            let body = self.lambda_body()?;
+            return Ok(Expr::Lambda { params, body: Rc::new(body) });
+        }
+        self.pipeline()
+    }
+
+    /// If the upcoming tokens form a lambda parameter list (`x ->` or
+    /// `(a, b) ->`), consumes them (through the `->`) and returns the
+    /// parameters. Otherwise consumes nothing and returns `None`.
+    fn try_match_lambda_params(&mut self) -> Option<Vec<Token>> {
+        if matches!(self.peek().token_type, TokenType::Identifier(_))
+            && matches!(
+                self.tokens.get(self.current + 1).map(|t| &t.token_type),
+                Some(TokenType::Arrow)
+            )
+        {
+            let param = self.advance().clone();
+            self.advance(); // consume '->'
+            return Some(vec![param]);
+        }
+
+        if self.check(&TokenType::LeftParen) {
+            // Scan ahead to the matching ')' without consuming anything, to
+            // tell a lambda parameter list apart from a parenthesized
+            // grouping expression.
+            let mut depth = 0;
+            let mut i = self.current;
+            loop {
+                match self.tokens.get(i)?.token_type {
+                    TokenType::LeftParen => depth += 1,
+                    TokenType::RightParen => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    TokenType::Eof => return None,
+                    _ => {}
+                }
+                i += 1;
+            }
+            let followed_by_arrow = matches!(
+                self.tokens.get(i + 1).map(|t| &t.token_type),
+                Some(TokenType::Arrow)
+            );
+            if !followed_by_arrow {
+                return None;
+            }
+
+            let start = self.current;
+            self.advance(); // consume '('
+            let mut params = Vec::new();
+            if !self.check(&TokenType::RightParen) {
+                loop {
+                    match self.match_identifier() {
+                        Some(param) => params.push(param),
+                        None => {
+                            self.current = start;
+                            return None;
+                        }
+                    }
+                    if !self.match_tokens(&[Comma]) {
+                        break;
+                    }
+                }
+            }
+            if self.consume(TokenType::RightParen, "Expect ')' after lambda parameters.").is_err()
+                || self.consume(TokenType::Arrow, "Expect '->' after lambda parameters.").is_err()
+            {
+                self.current = start;
+                return None;
+            }
+            return Some(params);
+        }
+
+        None
+    }
+
+    /// Parses the `(params) { body }` tail of an anonymous `func` expression,
+    /// having already consumed the `func` keyword. Mirrors
+    /// `Parser::function`'s header/body parsing, but builds an `Expr::Lambda`
+    /// instead of a named `Stmt::Function` since there's no name to bind.
+    fn function_expression(&mut self) -> Result<Expr> {
+        self.consume(TokenType::LeftParen, "Expect '(' after 'func'.")?;
+        let mut params = Vec::new();
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                if params.len() >= 255 {
+                    return Err(Error::Parse(parse_error(
+                        self.peek(),
+                        "Cannot have more than 255 parameters.",
+                    )));
+                }
+                params.push(self.consume(
+                    TokenType::Identifier("".to_string()),
+                    "Expect parameter name.",
+                )?.clone());
+                if !self.match_tokens(&[Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
+        self.consume(TokenType::LeftBrace, "Expect '{' before function body.")?;
+        let body = self.block()?;
+        Ok(Expr::Lambda { params, body: Rc::new(body) })
+    }
+
+    /// Parses a lambda body: a block for `(a, b) -> { ... }`, or a single
+    /// expression (implicitly returned) for `x -> expr`.
+    fn lambda_body(&mut self) -> Result<Vec<Stmt>> {
+        if self.match_tokens(&[TokenType::LeftBrace]) {
+            self.block()
+        } else {
+            let keyword = self.previous().clone(); // the '->' token
+            let value = self.expression()?;
+            Ok(vec![Stmt::Return {
+                keyword,
+                value: Some(value),
+            }])
+        }
+    }
+
+    /// Parses a pipeline expression: `x |> f` is sugar for `f(x)`, and the
+    /// operator is left-associative so `x |> f |> g` reads as `g(f(x))`.
+    /// `x |: f(a)` is the "fold" variant: it appends `x` as a trailing
+    /// argument to `f(a)` instead of calling `f` with `x` alone.
+    fn pipeline(&mut self) -> Result<Expr> {
+        let mut expr = self.assignment()?;
+
+        while self.match_tokens(&[TokenType::Pipe, TokenType::PipeFold]) {
+            let fold = self.previous().token_type == TokenType::PipeFold;
+            let func = self.assignment()?;
+            expr = Expr::Pipeline {
+                value: Box::new(expr),
+                func: Box::new(func),
+                fold,
+            };
+        }
+
+        Ok(expr)
     }
 
     /// Parses an assignment expression.
@@ -28,6 +177,12 @@ impl<'a> Parser<'a> {
                     name: name.clone(),
                     value: Box::new(value),
                 });
+            } else if let Expr::ArrayAccess { array, index } = expr {
+                return Ok(Expr::IndexSet {
+                    object: array,
+                    index,
+                    value: Box::new(value),
+                });
             }
 
             return Err(Error::Parse(parse_error(&equals, "Invalid assignment target.")));
@@ -204,6 +359,13 @@ impl<'a> Parser<'a> {
             return self.new_expression();
         }
         
+        // Check for an anonymous function expression: `func(a, b) { ... }`.
+        // Desugars to the same `Expr::Lambda` node the `(a, b) -> { ... }`
+        // arrow syntax produces.
+        if self.match_tokens(&[TokenType::Func]) {
+            return self.function_expression();
+        }
+
         // Check for 'delete' keyword
         if self.match_tokens(&[TokenType::Delete]) {
             let target = self.unary()?;  // Parse the target to delete
@@ -5,6 +5,7 @@ mod expr;
 mod stmt;
 mod expression_parser;
 mod statement_parser;
+pub mod optimize;
 
 use crate::error::{parse_error, InterpreterError as Error, ParseError, Result};
 use crate::lexer::{Token, TokenType};
@@ -12,35 +13,65 @@ use crate::lexer::{Token, TokenType};
 // Re-export the public API
 pub use expr::{Expr, Literal};
 pub use stmt::Stmt;
+pub use optimize::OptimizationLevel;
 
 /// The Parser converts a sequence of tokens into an Abstract Syntax Tree (AST).
 pub struct Parser<'a> {
     tokens: &'a [Token],
     current: usize,
+    /// How many `for`/`while` loops we're currently nested inside, so
+    /// `break`/`continue` can be rejected outside of a loop body.
+    loop_depth: usize,
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new parser with the given tokens.
     pub fn new(tokens: &'a [Token]) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            loop_depth: 0,
+        }
     }
 
     /// Parses the tokens into a vector of statements.
-    pub fn parse(&mut self) -> Result<Vec<Stmt>> {
+    ///
+    /// A parse error doesn't abort the whole parse: `declaration()` already
+    /// synchronizes to the next statement boundary on failure, so this
+    /// collects every error encountered across the file instead of stopping
+    /// at the first one. Returns `Ok` only if no errors were collected.
+    pub fn parse(&mut self) -> std::result::Result<Vec<Stmt>, Vec<Error>> {
         let mut statements = Vec::new();
-        
+        let mut errors = Vec::new();
+
         while !self.is_at_end() {
             // Check if the next token is EOF, if so, break the loop
             if self.peek().token_type == TokenType::Eof {
                 break;
             }
 
-            if let Some(statement) = self.declaration()? {
-                statements.push(statement);
+            match self.declaration() {
+                Ok(Some(statement)) => statements.push(statement),
+                Ok(None) => {}
+                Err(error) => errors.push(error),
             }
         }
-        
-        Ok(statements)
+
+        if errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parses the tokens into a vector of statements, then rewrites the
+    /// result according to `level` (constant folding, dead-code elimination).
+    pub fn parse_optimized(
+        &mut self,
+        level: OptimizationLevel,
+    ) -> std::result::Result<Vec<Stmt>, Vec<Error>> {
+        let statements = self.parse()?;
+        Ok(optimize::optimize(statements, level))
     }
 
     /// Checks if we've consumed all tokens.
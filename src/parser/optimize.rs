@@ -0,0 +1,391 @@
+//! Constant-folding and dead-code-elimination passes over the parsed AST.
+//!
+//! This runs after parsing and before resolution/interpretation. It never
+//! errors: operand combinations it can't safely fold (type mismatches,
+//! division by zero) are left as-is so they surface as ordinary runtime
+//! errors later, rather than failing to compile a program that would have
+//! run fine without optimization.
+
+use crate::lexer::{Token, TokenType};
+use crate::parser::{Expr, Stmt};
+
+/// How aggressively to rewrite the AST after parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// Return the AST unchanged.
+    None,
+    /// Fold constant arithmetic, string concatenation, and unary operators.
+    Simple,
+    /// Everything `Simple` does, plus constant-condition `if`/`and`/`or`
+    /// collapsing and dropping unreachable code after `return`.
+    Full,
+}
+
+/// Rewrites a parsed program according to `level`.
+pub fn optimize(statements: Vec<Stmt>, level: OptimizationLevel) -> Vec<Stmt> {
+    if level == OptimizationLevel::None {
+        return statements;
+    }
+
+    let statements: Vec<Stmt> = statements
+        .into_iter()
+        .map(|stmt| optimize_stmt(stmt, level))
+        .collect();
+
+    if level == OptimizationLevel::Full {
+        drop_dead_code(statements)
+    } else {
+        statements
+    }
+}
+
+/// Truncates a statement sequence right after its first `return`, since
+/// nothing after it can ever execute.
+fn drop_dead_code(statements: Vec<Stmt>) -> Vec<Stmt> {
+    let mut result = Vec::with_capacity(statements.len());
+    for stmt in statements {
+        let is_return = matches!(stmt, Stmt::Return { .. });
+        result.push(stmt);
+        if is_return {
+            break;
+        }
+    }
+    result
+}
+
+fn optimize_stmt(stmt: Stmt, level: OptimizationLevel) -> Stmt {
+    match stmt {
+        Stmt::Empty => Stmt::Empty,
+        Stmt::Expression(expr) => Stmt::Expression(optimize_expr(expr, level)),
+        Stmt::Print(expr) => Stmt::Print(optimize_expr(expr, level)),
+        Stmt::Var { name, initializer } => Stmt::Var {
+            name,
+            initializer: initializer.map(|e| optimize_expr(e, level)),
+        },
+        Stmt::Const { name, initializer } => Stmt::Const {
+            name,
+            initializer: optimize_expr(initializer, level),
+        },
+        Stmt::Block(statements) => {
+            let statements: Vec<Stmt> = statements
+                .into_iter()
+                .map(|s| optimize_stmt(s, level))
+                .collect();
+            let statements = if level == OptimizationLevel::Full {
+                drop_dead_code(statements)
+            } else {
+                statements
+            };
+            Stmt::Block(statements)
+        }
+        Stmt::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            let condition = optimize_expr(condition, level);
+            let then_branch = Box::new(optimize_stmt(*then_branch, level));
+            let else_branch = else_branch.map(|b| Box::new(optimize_stmt(*b, level)));
+
+            if level == OptimizationLevel::Full {
+                if let Some(truthy) = literal_truthiness(&condition) {
+                    return if truthy {
+                        *then_branch
+                    } else {
+                        match else_branch {
+                            Some(branch) => *branch,
+                            None => Stmt::Empty,
+                        }
+                    };
+                }
+            }
+
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            }
+        }
+        Stmt::While { condition, body } => Stmt::While {
+            condition: optimize_expr(condition, level),
+            body: Box::new(optimize_stmt(*body, level)),
+        },
+        Stmt::For {
+            initializer,
+            condition,
+            increment,
+            body,
+        } => Stmt::For {
+            initializer: initializer.map(|i| Box::new(optimize_stmt(*i, level))),
+            condition: condition.map(|c| optimize_expr(c, level)),
+            increment: increment.map(|i| optimize_expr(i, level)),
+            body: Box::new(optimize_stmt(*body, level)),
+        },
+        Stmt::Break(keyword) => Stmt::Break(keyword),
+        Stmt::Continue(keyword) => Stmt::Continue(keyword),
+        Stmt::Function { name, params, body } => {
+            let body = std::rc::Rc::try_unwrap(body).unwrap_or_else(|shared| (*shared).clone());
+            let body: Vec<Stmt> = body.into_iter().map(|s| optimize_stmt(s, level)).collect();
+            let body = if level == OptimizationLevel::Full {
+                drop_dead_code(body)
+            } else {
+                body
+            };
+            Stmt::Function {
+                name,
+                params,
+                body: std::rc::Rc::new(body),
+            }
+        }
+        Stmt::Return { keyword, value } => Stmt::Return {
+            keyword,
+            value: value.map(|e| optimize_expr(e, level)),
+        },
+        Stmt::Class {
+            name,
+            superclass,
+            methods,
+        } => Stmt::Class {
+            name,
+            superclass,
+            methods: methods
+                .into_iter()
+                .map(|m| optimize_stmt(m, level))
+                .collect(),
+        },
+    }
+}
+
+fn optimize_expr(expr: Expr, level: OptimizationLevel) -> Expr {
+    match expr {
+        Expr::Unary { operator, right } => {
+            let right = optimize_expr(*right, level);
+            fold_unary(operator, right)
+        }
+        Expr::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            let left = optimize_expr(*left, level);
+            let right = optimize_expr(*right, level);
+            fold_binary(left, operator, right)
+        }
+        Expr::Logical {
+            left,
+            operator,
+            right,
+        } => {
+            let left = optimize_expr(*left, level);
+            let right = optimize_expr(*right, level);
+            if level == OptimizationLevel::Full {
+                if let Some(truthy) = literal_truthiness(&left) {
+                    match (&operator.token_type, truthy) {
+                        (TokenType::Or, true) => return left,
+                        (TokenType::Or, false) => return right,
+                        (TokenType::And, false) => return left,
+                        (TokenType::And, true) => return right,
+                        _ => {}
+                    }
+                }
+            }
+            Expr::Logical {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            }
+        }
+        Expr::Grouping(inner) => {
+            let inner = optimize_expr(*inner, level);
+            // Parentheses around a literal carry no meaning once parsing is
+            // done (precedence is already baked into the tree shape), so
+            // drop them instead of wrapping a no-op node around the fold.
+            match inner {
+                Expr::Literal(token) => Expr::Literal(token),
+                other => Expr::Grouping(Box::new(other)),
+            }
+        }
+        Expr::Call { callee, arguments } => Expr::Call {
+            callee: Box::new(optimize_expr(*callee, level)),
+            arguments: arguments.into_iter().map(|a| optimize_expr(a, level)).collect(),
+        },
+        Expr::Get { object, name } => Expr::Get {
+            object: Box::new(optimize_expr(*object, level)),
+            name,
+        },
+        Expr::Set { object, name, value } => Expr::Set {
+            object: Box::new(optimize_expr(*object, level)),
+            name,
+            value: Box::new(optimize_expr(*value, level)),
+        },
+        Expr::IndexSet { object, index, value } => Expr::IndexSet {
+            object: Box::new(optimize_expr(*object, level)),
+            index: Box::new(optimize_expr(*index, level)),
+            value: Box::new(optimize_expr(*value, level)),
+        },
+        Expr::Assign { name, value } => Expr::Assign {
+            name,
+            value: Box::new(optimize_expr(*value, level)),
+        },
+        Expr::New { class, arguments } => Expr::New {
+            class: Box::new(optimize_expr(*class, level)),
+            arguments: arguments.into_iter().map(|a| optimize_expr(a, level)).collect(),
+        },
+        Expr::CustomNew {
+            allocator,
+            class,
+            arguments,
+        } => Expr::CustomNew {
+            allocator: Box::new(optimize_expr(*allocator, level)),
+            class: Box::new(optimize_expr(*class, level)),
+            arguments: arguments.into_iter().map(|a| optimize_expr(a, level)).collect(),
+        },
+        Expr::Delete { target } => Expr::Delete {
+            target: Box::new(optimize_expr(*target, level)),
+        },
+        Expr::DeleteArray { target } => Expr::DeleteArray {
+            target: Box::new(optimize_expr(*target, level)),
+        },
+        Expr::Dereference { expression } => Expr::Dereference {
+            expression: Box::new(optimize_expr(*expression, level)),
+        },
+        Expr::AddressOf { expression } => Expr::AddressOf {
+            expression: Box::new(optimize_expr(*expression, level)),
+        },
+        Expr::NewArray { element_type, size } => Expr::NewArray {
+            element_type,
+            size: Box::new(optimize_expr(*size, level)),
+        },
+        Expr::ArrayAccess { array, index } => Expr::ArrayAccess {
+            array: Box::new(optimize_expr(*array, level)),
+            index: Box::new(optimize_expr(*index, level)),
+        },
+        Expr::Lambda { params, body } => {
+            let body = std::rc::Rc::try_unwrap(body).unwrap_or_else(|shared| (*shared).clone());
+            let body: Vec<Stmt> = body.into_iter().map(|s| optimize_stmt(s, level)).collect();
+            Expr::Lambda {
+                params,
+                body: std::rc::Rc::new(body),
+            }
+        }
+        Expr::Pipeline { value, func, fold } => Expr::Pipeline {
+            value: Box::new(optimize_expr(*value, level)),
+            func: Box::new(optimize_expr(*func, level)),
+            fold,
+        },
+        // Literals, variables, this/super have no sub-expressions to fold.
+        other => other,
+    }
+}
+
+/// Extracts a number from a literal expression, if it is one.
+fn as_number(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Literal(token) => match token.token_type {
+            TokenType::Number(n) => Some(n),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Extracts a string from a literal expression, if it is one.
+fn as_string(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Literal(token) => match &token.token_type {
+            TokenType::String(s) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the truthiness of a literal expression, if it is one
+/// (`false`/`nil` are falsy, everything else truthy), mirroring
+/// `Literal::is_truthy`.
+fn literal_truthiness(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Literal(token) => match &token.token_type {
+            TokenType::False => Some(false),
+            TokenType::Nil => Some(false),
+            TokenType::True | TokenType::Number(_) | TokenType::String(_) => Some(true),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn number_token(n: f64, line: usize) -> Expr {
+    Expr::Literal(Token::new(TokenType::Number(n), n.to_string(), line))
+}
+
+fn string_token(s: String, line: usize) -> Expr {
+    Expr::Literal(Token::new(TokenType::String(s.clone()), s, line))
+}
+
+fn bool_token(b: bool, line: usize) -> Expr {
+    let token_type = if b { TokenType::True } else { TokenType::False };
+    Expr::Literal(Token::new(token_type, b.to_string(), line))
+}
+
+fn fold_unary(operator: Token, right: Expr) -> Expr {
+    let line = operator.line;
+    match operator.token_type {
+        TokenType::Minus => {
+            if let Some(n) = as_number(&right) {
+                return number_token(-n, line);
+            }
+        }
+        TokenType::Bang => {
+            if let Some(truthy) = literal_truthiness(&right) {
+                return bool_token(!truthy, line);
+            }
+        }
+        _ => {}
+    }
+    Expr::Unary {
+        operator,
+        right: Box::new(right),
+    }
+}
+
+fn fold_binary(left: Expr, operator: Token, right: Expr) -> Expr {
+    let line = operator.line;
+
+    if let (Some(a), Some(b)) = (as_number(&left), as_number(&right)) {
+        let folded = match operator.token_type {
+            TokenType::Plus => Some(number_token(a + b, line)),
+            TokenType::Minus => Some(number_token(a - b, line)),
+            TokenType::Star => Some(number_token(a * b, line)),
+            // Division by zero must surface as a runtime error, not a
+            // compile-time one, so leave it unfolded.
+            TokenType::Slash if b != 0.0 => Some(number_token(a / b, line)),
+            TokenType::Greater => Some(bool_token(a > b, line)),
+            TokenType::GreaterEqual => Some(bool_token(a >= b, line)),
+            TokenType::Less => Some(bool_token(a < b, line)),
+            TokenType::LessEqual => Some(bool_token(a <= b, line)),
+            TokenType::EqualEqual => Some(bool_token(a == b, line)),
+            TokenType::BangEqual => Some(bool_token(a != b, line)),
+            _ => None,
+        };
+        if let Some(folded) = folded {
+            return folded;
+        }
+    } else if let (Some(a), Some(b)) = (as_string(&left), as_string(&right)) {
+        let folded = match operator.token_type {
+            TokenType::Plus => Some(string_token(format!("{}{}", a, b), line)),
+            TokenType::EqualEqual => Some(bool_token(a == b, line)),
+            TokenType::BangEqual => Some(bool_token(a != b, line)),
+            _ => None,
+        };
+        if let Some(folded) = folded {
+            return folded;
+        }
+    }
+
+    Expr::Binary {
+        left: Box::new(left),
+        operator,
+        right: Box::new(right),
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::*;
 use crate::lexer::TokenType::*;
+use std::rc::Rc;
 
 impl<'a> Parser<'a> {
     /// Parses a declaration.
@@ -98,6 +99,10 @@ impl<'a> Parser<'a> {
             self.while_statement()
         } else if self.match_tokens(&[For]) {
             self.for_statement()
+        } else if self.match_tokens(&[Break]) {
+            self.break_statement()
+        } else if self.match_tokens(&[Continue]) {
+            self.continue_statement()
         } else if self.match_tokens(&[Return]) {
             self.return_statement()
         } else {
@@ -124,7 +129,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Parses a block of statements.
-    fn block(&mut self) -> Result<Vec<Stmt>> {
+    pub(super) fn block(&mut self) -> Result<Vec<Stmt>> {
         let mut statements = Vec::new();
 
         while !self.check(&RightBrace) && !self.is_at_end() {
@@ -175,8 +180,12 @@ impl<'a> Parser<'a> {
         let condition = self.expression()?;
         self.consume(RightParen, "Expect ')' after condition.")?;
 
+        self.loop_depth += 1;
+        let body = self.statement();
+        self.loop_depth -= 1;
+
         let body = Box::new(
-            self.statement()?
+            body?
                 .ok_or_else(|| Error::Parse(ParseError::Custom(
                     format!("{} at '{}'", "Expect statement after 'while'.", self.peek().lexeme)
                 )))?,
@@ -185,7 +194,7 @@ impl<'a> Parser<'a> {
         Ok(Some(Stmt::While { condition, body }))
     }
 
-    /// Parses a for statement.
+    /// Parses a native for statement: `for (init; cond; incr) body`.
     fn for_statement(&mut self) -> Result<Option<Stmt>> {
         self.consume(LeftParen, "Expect '(' after 'for'.")?;
 
@@ -193,64 +202,71 @@ impl<'a> Parser<'a> {
         let initializer = if self.match_tokens(&[Semicolon]) {
             None
         } else if self.match_tokens(&[Var]) {
-            self.var_declaration()?
+            self.var_declaration()?.map(Box::new)
         } else if self.match_tokens(&[Const]) {
-            self.const_declaration()?
+            self.const_declaration()?.map(Box::new)
         } else {
-            Some(self.expression_statement()?)
+            Some(Box::new(self.expression_statement()?))
         };
 
         // Condition
         let condition = if !self.check(&Semicolon) {
-            self.expression()
+            Some(self.expression()?)
         } else {
-            // Default to true if condition is omitted
-            Ok(Expr::Literal(Token::new(
-                TokenType::True,
-                "true".to_string(),
-                self.previous().line,
-            )))
-        }?;
-
+            None
+        };
         self.consume(Semicolon, "Expect ';' after loop condition.")?;
 
         // Increment
         let increment = if !self.check(&RightParen) {
-            let expr = self.expression()?;
-            Some(expr)
+            Some(self.expression()?)
         } else {
             None
         };
-
         self.consume(RightParen, "Expect ')' after for clauses.")?;
 
-        let mut body = self.statement()?;
+        self.loop_depth += 1;
+        let body = self.statement();
+        self.loop_depth -= 1;
 
-        // Desugar the for loop into a while loop
-        if let Some(inc) = increment {
-            if let Some(stmt) = &mut body {
-                // Wrap the body in a block with the increment at the end
-                let nil_token = Token::new(TokenType::Nil, "nil".to_string(), self.previous().line);
-                let old_stmt = std::mem::replace(stmt, Stmt::Expression(Expr::Literal(nil_token)));
-                *stmt = Stmt::Block(vec![old_stmt, Stmt::Expression(inc)]);
-            }
-        }
+        let body = Box::new(body?.ok_or_else(|| {
+            Error::Parse(ParseError::Custom(
+                format!("{} at '{}'", "Expect statement after 'for'.", self.peek().lexeme),
+            ))
+        })?);
 
-        let while_loop = Stmt::While {
+        Ok(Some(Stmt::For {
+            initializer,
             condition,
-            body: Box::new(body.unwrap_or_else(|| {
-                let nil_token = Token::new(TokenType::Nil, "nil".to_string(), self.previous().line);
-                Stmt::Expression(Expr::Literal(nil_token))
-            })),
-        };
+            increment,
+            body,
+        }))
+    }
 
-        let result = if let Some(init) = initializer {
-            Stmt::Block(vec![init, while_loop])
-        } else {
-            while_loop
-        };
+    /// Parses a `break` statement. Rejected outside of a loop body.
+    fn break_statement(&mut self) -> Result<Option<Stmt>> {
+        let keyword = self.previous().clone();
+        if self.loop_depth == 0 {
+            return Err(Error::Parse(ParseError::Custom(format!(
+                "'break' outside of a loop at '{}'.",
+                keyword.lexeme
+            ))));
+        }
+        self.consume(Semicolon, "Expect ';' after 'break'.")?;
+        Ok(Some(Stmt::Break(keyword)))
+    }
 
-        Ok(Some(result))
+    /// Parses a `continue` statement. Rejected outside of a loop body.
+    fn continue_statement(&mut self) -> Result<Option<Stmt>> {
+        let keyword = self.previous().clone();
+        if self.loop_depth == 0 {
+            return Err(Error::Parse(ParseError::Custom(format!(
+                "'continue' outside of a loop at '{}'.",
+                keyword.lexeme
+            ))));
+        }
+        self.consume(Semicolon, "Expect ';' after 'continue'.")?;
+        Ok(Some(Stmt::Continue(keyword)))
     }
 
     /// Parses a function declaration.
@@ -295,7 +311,7 @@ impl<'a> Parser<'a> {
         Ok(Stmt::Function {
             name,
             params: parameters,
-            body,
+            body: Rc::new(body),
         })
     }
 
@@ -352,7 +368,7 @@ impl<'a> Parser<'a> {
             }
 
             match &self.peek().token_type {
-                Var | Const | For | If | While | Print | Return | Func | Class => return,
+                Var | Const | For | If | While | Print | Return | Func | Class | Break | Continue => return,
                 _ => {}
             }
 
@@ -1,8 +1,9 @@
 //! Statement nodes for the Demon language AST.
 
-use crate::lexer::Token;
+use crate::lexer::{Span, Token};
 use crate::parser::expr::Expr;
 use std::fmt;
+use std::rc::Rc;
 
 /// Represents a statement in the Demon language.
 #[derive(Debug, Clone)]
@@ -42,12 +43,34 @@ pub enum Stmt {
         condition: Expr,
         body: Box<Stmt>,
     },
-    
+
+    /// A native for loop (e.g., `for (var i = 0; i < 10; i = i + 1) { ... }`)
+    For {
+        initializer: Option<Box<Stmt>>,
+        condition: Option<Expr>,
+        increment: Option<Expr>,
+        body: Box<Stmt>,
+    },
+
+    /// A `break` statement, exiting the innermost enclosing loop.
+    Break(Token),
+
+    /// A `continue` statement, skipping to the next iteration of the
+    /// innermost enclosing loop.
+    Continue(Token),
+
     /// A function declaration (e.g., `func add(a, b) { return a + b; }`)
+    ///
+    /// `body` is `Rc`-shared rather than owned outright so that a `Function`
+    /// built from this declaration (and every subsequent call to it) can
+    /// clone the *handle*, not the statements: the resolver records scope
+    /// depths keyed by each inner `Expr`'s address, and those addresses must
+    /// stay the ones the interpreter actually evaluates or every lookup
+    /// inside a function body silently falls back to dynamic scoping.
     Function {
         name: Token,
         params: Vec<Token>,
-        body: Vec<Stmt>,
+        body: Rc<Vec<Stmt>>,
     },
     
     /// A return statement (e.g., `return 42;`)
@@ -64,6 +87,86 @@ pub enum Stmt {
     }
 }
 
+impl Stmt {
+    /// Returns the first token of the statement for error reporting and
+    /// spans. Returns `None` for `Stmt::Empty`, which carries no token.
+    pub fn first_token(&self) -> Option<Token> {
+        match self {
+            Stmt::Empty => None,
+            Stmt::Expression(expr) => Some(expr.first_token()),
+            Stmt::Print(expr) => Some(expr.first_token()),
+            Stmt::Var { name, .. } => Some(name.clone()),
+            Stmt::Const { name, .. } => Some(name.clone()),
+            Stmt::Block(statements) => statements.first().and_then(Stmt::first_token),
+            Stmt::If { condition, .. } => Some(condition.first_token()),
+            Stmt::While { condition, .. } => Some(condition.first_token()),
+            Stmt::For {
+                initializer,
+                condition,
+                body,
+                ..
+            } => initializer
+                .as_deref()
+                .and_then(Stmt::first_token)
+                .or_else(|| condition.as_ref().map(Expr::first_token))
+                .or_else(|| body.first_token()),
+            Stmt::Break(keyword) => Some(keyword.clone()),
+            Stmt::Continue(keyword) => Some(keyword.clone()),
+            Stmt::Function { name, .. } => Some(name.clone()),
+            Stmt::Return { keyword, .. } => Some(keyword.clone()),
+            Stmt::Class { name, .. } => Some(name.clone()),
+        }
+    }
+
+    /// Returns the last token of the statement for error reporting and spans.
+    pub fn last_token(&self) -> Option<Token> {
+        match self {
+            Stmt::Empty => None,
+            Stmt::Expression(expr) => Some(expr.last_token()),
+            Stmt::Print(expr) => Some(expr.last_token()),
+            Stmt::Var { name, initializer } => initializer
+                .as_ref()
+                .map(Expr::last_token)
+                .or_else(|| Some(name.clone())),
+            Stmt::Const { initializer, .. } => Some(initializer.last_token()),
+            Stmt::Block(statements) => statements.last().and_then(Stmt::last_token),
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => else_branch
+                .as_ref()
+                .and_then(|stmt| stmt.last_token())
+                .or_else(|| then_branch.last_token()),
+            Stmt::While { body, .. } => body.last_token(),
+            Stmt::For { body, .. } => body.last_token(),
+            Stmt::Break(keyword) => Some(keyword.clone()),
+            Stmt::Continue(keyword) => Some(keyword.clone()),
+            Stmt::Function { name, body, .. } => body
+                .last()
+                .and_then(Stmt::last_token)
+                .or_else(|| Some(name.clone())),
+            Stmt::Return { keyword, value } => value
+                .as_ref()
+                .map(Expr::last_token)
+                .or_else(|| Some(keyword.clone())),
+            Stmt::Class { name, methods, .. } => methods
+                .last()
+                .and_then(Stmt::last_token)
+                .or_else(|| Some(name.clone())),
+        }
+    }
+
+    /// Returns the full source span of the statement, from its first token
+    /// to its last. `Stmt::Empty` has no tokens, so it yields `Span::default()`.
+    pub fn span(&self) -> Span {
+        match (self.first_token(), self.last_token()) {
+            (Some(first), Some(last)) => first.span.to(&last.span),
+            _ => Span::default(),
+        }
+    }
+}
+
 impl fmt::Display for Stmt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -101,10 +204,32 @@ impl fmt::Display for Stmt {
             Stmt::While { condition, body } => {
                 write!(f, "while ({}) {}", condition, body)
             }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                write!(f, "for (")?;
+                match initializer {
+                    Some(init) => write!(f, "{}", init)?,
+                    None => write!(f, ";")?,
+                }
+                match condition {
+                    Some(cond) => write!(f, " {};", cond)?,
+                    None => write!(f, " ;")?,
+                }
+                if let Some(inc) = increment {
+                    write!(f, " {}", inc)?;
+                }
+                write!(f, ") {}", body)
+            }
+            Stmt::Break(_) => write!(f, "break;"),
+            Stmt::Continue(_) => write!(f, "continue;"),
             Stmt::Function { name, params, body } => {
                 let param_list: Vec<String> = params.iter().map(|p| p.lexeme.clone()).collect();
                 write!(f, "func {}({}) ", name.lexeme, param_list.join(", "))?;
-                write!(f, "{}", Stmt::Block(body.clone()))
+                write!(f, "{}", Stmt::Block((**body).clone()))
             }
             Stmt::Return { keyword, value } => {
                 if let Some(expr) = value {
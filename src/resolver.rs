@@ -0,0 +1,408 @@
+//! Static scope resolution for the Demon language.
+//!
+//! The interpreter walks a chain of `Environment`s at runtime to find
+//! variables, which is slow and (for closures declared inside loops or
+//! blocks) fragile. This pass runs once between parsing and interpretation
+//! and figures out, for every variable reference, how many enclosing scopes
+//! to hop to reach its binding. The result is a side table keyed by the
+//! `Expr`'s address, mirroring `Interpreter::locals` / `look_up_variable`,
+//! so the interpreter can jump straight to the right environment instead of
+//! walking outward one frame at a time.
+//!
+//! Along the way it catches a few errors statically rather than at runtime:
+//! reading a local variable from inside its own initializer, `return` outside
+//! a function, `this` outside a class, and `super` outside a subclass.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{InterpreterError as Error, Result};
+use crate::lexer::Token;
+use crate::parser::{Expr, Stmt};
+
+/// Maps an `Expr` node's address to the number of enclosing scopes between
+/// it and the scope that declares the variable it refers to.
+pub type Locals = HashMap<usize, usize>;
+
+/// What kind of function (if any) the resolver is currently inside, used to
+/// reject `return` at the top level and to tell an initializer's implicit
+/// `return this` apart from an ordinary method's `return <value>`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FunctionType {
+    None,
+    Function,
+    Method,
+    Initializer,
+}
+
+/// What kind of class (if any) the resolver is currently inside, used to
+/// reject stray `this`/`super` and to tell a subclass apart from a class
+/// with no superclass (where `super` makes no sense).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ClassType {
+    None,
+    Class,
+    Subclass,
+}
+
+/// Walks a program's statements and resolves every variable and assignment
+/// expression to a scope depth.
+pub struct Resolver {
+    /// Each scope maps a name to whether its initializer has finished
+    /// resolving yet. A name is `false` while its own initializer is being
+    /// resolved, which lets us catch `var x = x;` style self-reference.
+    scopes: Vec<HashMap<String, bool>>,
+    /// Names declared with `const` in each scope on `scopes`, tracked in
+    /// parallel so `Expr::Assign` can reject a write to one statically
+    /// instead of waiting for `Environment::assign` to fail at runtime.
+    const_scopes: Vec<HashSet<String>>,
+    locals: Locals,
+    /// The function we're currently resolving inside, if any. Saved and
+    /// restored around `resolve_function` so nested functions don't leak
+    /// their context into the enclosing one.
+    current_function: FunctionType,
+    /// The class we're currently resolving inside, if any. Saved and
+    /// restored around `Stmt::Class` the same way.
+    current_class: ClassType,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self {
+            scopes: Vec::new(),
+            const_scopes: Vec::new(),
+            locals: Locals::new(),
+            current_function: FunctionType::None,
+            current_class: ClassType::None,
+        }
+    }
+
+    /// Resolves a whole program, returning the computed scope depths.
+    pub fn resolve(mut self, statements: &[Stmt]) -> Result<Locals> {
+        self.resolve_stmts(statements)?;
+        Ok(self.locals)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+        self.const_scopes.push(HashSet::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+        self.const_scopes.pop();
+    }
+
+    fn declare(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), false);
+        }
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), true);
+        }
+    }
+
+    /// Declares and defines a `const` binding in one step, additionally
+    /// marking it in `const_scopes` so a later assignment to it is rejected.
+    fn declare_const(&mut self, name: &Token) {
+        self.declare(name);
+        self.define(name);
+        if let Some(scope) = self.const_scopes.last_mut() {
+            scope.insert(name.lexeme.clone());
+        }
+    }
+
+    fn resolve_local(&mut self, expr: &Expr, name: &str) {
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if scope.contains_key(name) {
+                self.locals.insert(expr as *const Expr as usize, depth);
+                return;
+            }
+        }
+        // Not found in any scope: treat it as global, resolved at runtime
+        // via `Environment::get` the same way unresolved variables already are.
+    }
+
+    /// Checks whether `name` refers to a `const` binding in the nearest
+    /// enclosing scope that declares it — the same scope `resolve_local`
+    /// would resolve an `Expr::Variable` with this name to.
+    fn is_const(&self, name: &str) -> bool {
+        for (scope, const_scope) in self.scopes.iter().rev().zip(self.const_scopes.iter().rev()) {
+            if scope.contains_key(name) {
+                return const_scope.contains(name);
+            }
+        }
+        false
+    }
+
+    fn resolve_function(&mut self, params: &[Token], body: &[Stmt], kind: FunctionType) -> Result<()> {
+        let enclosing_function = self.current_function;
+        self.current_function = kind;
+
+        self.begin_scope();
+        for param in params {
+            self.declare(param);
+            self.define(param);
+        }
+        let result = self.resolve_stmts(body);
+        self.end_scope();
+
+        self.current_function = enclosing_function;
+        result
+    }
+
+    fn resolve_stmts(&mut self, statements: &[Stmt]) -> Result<()> {
+        for statement in statements {
+            self.resolve_stmt(statement)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_stmt(&mut self, stmt: &Stmt) -> Result<()> {
+        match stmt {
+            Stmt::Empty => Ok(()),
+            Stmt::Expression(expr) | Stmt::Print(expr) => self.resolve_expr(expr),
+            Stmt::Var { name, initializer } => {
+                self.declare(name);
+                if let Some(init) = initializer {
+                    self.resolve_expr(init)?;
+                }
+                self.define(name);
+                Ok(())
+            }
+            Stmt::Const { name, initializer } => {
+                self.declare(name);
+                self.resolve_expr(initializer)?;
+                self.declare_const(name);
+                Ok(())
+            }
+            Stmt::Block(statements) => {
+                self.begin_scope();
+                let result = self.resolve_stmts(statements);
+                self.end_scope();
+                result
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.resolve_expr(condition)?;
+                self.resolve_stmt(then_branch)?;
+                if let Some(else_branch) = else_branch {
+                    self.resolve_stmt(else_branch)?;
+                }
+                Ok(())
+            }
+            Stmt::While { condition, body } => {
+                self.resolve_expr(condition)?;
+                self.resolve_stmt(body)
+            }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                self.begin_scope();
+                if let Some(init) = initializer {
+                    self.resolve_stmt(init)?;
+                }
+                if let Some(condition) = condition {
+                    self.resolve_expr(condition)?;
+                }
+                self.resolve_stmt(body)?;
+                if let Some(increment) = increment {
+                    self.resolve_expr(increment)?;
+                }
+                self.end_scope();
+                Ok(())
+            }
+            Stmt::Break(_) | Stmt::Continue(_) => Ok(()),
+            Stmt::Function { name, params, body } => {
+                self.declare(name);
+                self.define(name);
+                self.resolve_function(params, body, FunctionType::Function)
+            }
+            Stmt::Return { value, keyword } => {
+                if self.current_function == FunctionType::None {
+                    return Err(Error::General(format!(
+                        "Can't return from top-level code (line {}).",
+                        keyword.line
+                    )));
+                }
+                if let Some(value) = value {
+                    if self.current_function == FunctionType::Initializer {
+                        return Err(Error::General(
+                            "Can't return a value from an initializer.".to_string(),
+                        ));
+                    }
+                    self.resolve_expr(value)?;
+                }
+                Ok(())
+            }
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+            } => {
+                let enclosing_class = self.current_class;
+                self.current_class = ClassType::Class;
+
+                self.declare(name);
+                self.define(name);
+
+                let has_superclass = superclass.is_some();
+                if let Some(superclass) = superclass {
+                    self.current_class = ClassType::Subclass;
+                    self.resolve_expr(superclass)?;
+                    self.begin_scope();
+                    self.scopes.last_mut().unwrap().insert("super".to_string(), true);
+                }
+
+                self.begin_scope();
+                self.scopes.last_mut().unwrap().insert("this".to_string(), true);
+
+                for method in methods {
+                    if let Stmt::Function { name, params, body } = method {
+                        let kind = if name.lexeme == "init" {
+                            FunctionType::Initializer
+                        } else {
+                            FunctionType::Method
+                        };
+                        self.resolve_function(params, body, kind)?;
+                    }
+                }
+
+                self.end_scope();
+                if has_superclass {
+                    self.end_scope();
+                }
+                self.current_class = enclosing_class;
+                Ok(())
+            }
+        }
+    }
+
+    fn resolve_expr(&mut self, expr: &Expr) -> Result<()> {
+        match expr {
+            Expr::Literal(_) => Ok(()),
+            Expr::Variable(name) => {
+                if let Some(scope) = self.scopes.last() {
+                    if scope.get(&name.lexeme) == Some(&false) {
+                        return Err(Error::General(format!(
+                            "Can't read local variable '{}' in its own initializer.",
+                            name.lexeme
+                        )));
+                    }
+                }
+                self.resolve_local(expr, &name.lexeme);
+                Ok(())
+            }
+            Expr::Assign { name, value } => {
+                if self.is_const(&name.lexeme) {
+                    return Err(Error::General(format!(
+                        "Cannot assign to constant '{}'.",
+                        name.lexeme
+                    )));
+                }
+                self.resolve_expr(value)?;
+                self.resolve_local(expr, &name.lexeme);
+                Ok(())
+            }
+            Expr::This(_) => {
+                if self.current_class == ClassType::None {
+                    return Err(Error::General(
+                        "Can't use 'this' outside of a class.".to_string(),
+                    ));
+                }
+                self.resolve_local(expr, "this");
+                Ok(())
+            }
+            Expr::Super { .. } => {
+                if self.current_class == ClassType::None {
+                    return Err(Error::General(
+                        "Can't use 'super' outside of a class.".to_string(),
+                    ));
+                } else if self.current_class != ClassType::Subclass {
+                    return Err(Error::General(
+                        "Can't use 'super' in a class with no superclass.".to_string(),
+                    ));
+                }
+                self.resolve_local(expr, "super");
+                Ok(())
+            }
+            Expr::Unary { right, .. } => self.resolve_expr(right),
+            Expr::Binary { left, right, .. } | Expr::Logical { left, right, .. } => {
+                self.resolve_expr(left)?;
+                self.resolve_expr(right)
+            }
+            Expr::Grouping(expr) => self.resolve_expr(expr),
+            Expr::Call { callee, arguments } => {
+                self.resolve_expr(callee)?;
+                self.resolve_exprs(arguments)
+            }
+            Expr::Get { object, .. } => self.resolve_expr(object),
+            Expr::Set { object, value, .. } => {
+                self.resolve_expr(value)?;
+                self.resolve_expr(object)
+            }
+            Expr::IndexSet { object, index, value } => {
+                self.resolve_expr(value)?;
+                self.resolve_expr(object)?;
+                self.resolve_expr(index)
+            }
+            Expr::New { class, arguments } => {
+                self.resolve_expr(class)?;
+                self.resolve_exprs(arguments)
+            }
+            Expr::CustomNew {
+                allocator,
+                class,
+                arguments,
+            } => {
+                self.resolve_expr(allocator)?;
+                self.resolve_expr(class)?;
+                self.resolve_exprs(arguments)
+            }
+            Expr::Delete { target } | Expr::DeleteArray { target } => self.resolve_expr(target),
+            Expr::Dereference { expression } | Expr::AddressOf { expression } => {
+                self.resolve_expr(expression)
+            }
+            Expr::NewArray { size, .. } => self.resolve_expr(size),
+            Expr::ArrayAccess { array, index } => {
+                self.resolve_expr(array)?;
+                self.resolve_expr(index)
+            }
+            Expr::Lambda { params, body } => {
+                self.resolve_function(params, body, FunctionType::Function)
+            }
+            Expr::Pipeline { value, func, .. } => {
+                self.resolve_expr(value)?;
+                self.resolve_expr(func)
+            }
+        }
+    }
+
+    fn resolve_exprs(&mut self, exprs: &[Expr]) -> Result<()> {
+        for expr in exprs {
+            self.resolve_expr(expr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Convenience wrapper: resolves a program and returns the computed scope
+/// depths, or an error if resolution fails (e.g. a self-referencing
+/// initializer).
+pub fn resolve(statements: &[Stmt]) -> Result<Locals> {
+    Resolver::new().resolve(statements)
+}
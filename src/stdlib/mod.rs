@@ -1,143 +1,459 @@
 //! Standard library for the Demon programming language.
 
+use std::cmp::Ordering;
 use std::io;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::interpreter::{Interpreter, NativeFunction};
+use crate::interpreter::{Arity, Callable, Interpreter, LazyIter, NativeFunction};
 use crate::{Literal, Result};
 
 /// Registers all standard library functions in the global environment.
 pub fn register_stdlib(interpreter: &mut Interpreter) {
     // I/O functions
     interpreter.globals().borrow_mut().define(
-        "print".to_string(),
-        Literal::Callable(Box::new(NativeFunction::new("print", usize::MAX, print))),
+        crate::interner::intern("print"),
+        Literal::Callable(Box::new(NativeFunction::new("print", Arity::AtLeast(0), print))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "input".to_string(),
+        crate::interner::intern("input"),
         Literal::Callable(Box::new(NativeFunction::new("input", 0, input))),
     );
     
     // Time functions
     interpreter.globals().borrow_mut().define(
-        "time".to_string(),
+        crate::interner::intern("time"),
         Literal::Callable(Box::new(NativeFunction::new("time", 0, time))),
     );
 
+    // Random functions
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("random"),
+        Literal::Callable(Box::new(NativeFunction::new("random", 0, random))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("random_int"),
+        Literal::Callable(Box::new(NativeFunction::new("random_int", 2, random_int))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("random_choice"),
+        Literal::Callable(Box::new(NativeFunction::new("random_choice", 1, random_choice))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("shuffle"),
+        Literal::Callable(Box::new(NativeFunction::new("shuffle", 1, shuffle))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("seed"),
+        Literal::Callable(Box::new(NativeFunction::new("seed", 1, seed))),
+    );
+
     // Type conversion functions
     interpreter.globals().borrow_mut().define(
-        "to_string".to_string(),
+        crate::interner::intern("to_string"),
         Literal::Callable(Box::new(NativeFunction::new("to_string", 1, to_string))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "to_number".to_string(),
+        crate::interner::intern("to_number"),
         Literal::Callable(Box::new(NativeFunction::new("to_number", 1, to_number))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "to_bool".to_string(),
+        crate::interner::intern("to_bool"),
         Literal::Callable(Box::new(NativeFunction::new("to_bool", 1, to_bool))),
     );
 
     // Math functions
     interpreter.globals().borrow_mut().define(
-        "abs".to_string(),
+        crate::interner::intern("abs"),
         Literal::Callable(Box::new(NativeFunction::new("abs", 1, abs))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "sqrt".to_string(),
+        crate::interner::intern("sqrt"),
         Literal::Callable(Box::new(NativeFunction::new("sqrt", 1, sqrt))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "pow".to_string(),
+        crate::interner::intern("pow"),
         Literal::Callable(Box::new(NativeFunction::new("pow", 2, pow))),
     );
 
     // String functions
     interpreter.globals().borrow_mut().define(
-        "len".to_string(),
+        crate::interner::intern("len"),
         Literal::Callable(Box::new(NativeFunction::new("len", 1, len))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "substring".to_string(),
-        Literal::Callable(Box::new(NativeFunction::new("substring", usize::MAX, substring))),
+        crate::interner::intern("substring"),
+        Literal::Callable(Box::new(NativeFunction::new("substring", 3, substring))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("split"),
+        Literal::Callable(Box::new(NativeFunction::new("split", 2, split))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("join"),
+        Literal::Callable(Box::new(NativeFunction::new("join", 2, join))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("replace"),
+        Literal::Callable(Box::new(NativeFunction::new("replace", 3, replace))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("to_upper"),
+        Literal::Callable(Box::new(NativeFunction::new("to_upper", 1, to_upper))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("to_lower"),
+        Literal::Callable(Box::new(NativeFunction::new("to_lower", 1, to_lower))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("trim"),
+        Literal::Callable(Box::new(NativeFunction::new("trim", 1, trim))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("starts_with"),
+        Literal::Callable(Box::new(NativeFunction::new("starts_with", 2, starts_with))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("ends_with"),
+        Literal::Callable(Box::new(NativeFunction::new("ends_with", 2, ends_with))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("index_of"),
+        Literal::Callable(Box::new(NativeFunction::new("index_of", 2, index_of))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("contains"),
+        Literal::Callable(Box::new(NativeFunction::new("contains", 2, contains))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("repeat"),
+        Literal::Callable(Box::new(NativeFunction::new("repeat", 2, repeat))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("chr"),
+        Literal::Callable(Box::new(NativeFunction::new("chr", 1, chr))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("ord"),
+        Literal::Callable(Box::new(NativeFunction::new("ord", 1, ord))),
     );
 
     // Array functions
     interpreter.globals().borrow_mut().define(
-        "array".to_string(),
-        Literal::Callable(Box::new(NativeFunction::new("array", usize::MAX, array))),
+        crate::interner::intern("array"),
+        Literal::Callable(Box::new(NativeFunction::new("array", Arity::AtLeast(0), array))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "push".to_string(),
+        crate::interner::intern("push"),
         Literal::Callable(Box::new(NativeFunction::new("push", 2, push))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "pop".to_string(),
+        crate::interner::intern("pop"),
         Literal::Callable(Box::new(NativeFunction::new("pop", 1, pop))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map".to_string(),
+        crate::interner::intern("map"),
         Literal::Callable(Box::new(NativeFunction::new("map", 2, map))),
     );
 
+    // Lazy iterator subsystem: `range`/`iter` produce a `Literal::Iterator`,
+    // the combinators each wrap a source iterator in a new one that pulls
+    // from it on demand, and only the reducers (plus `find`/`any`/`all`
+    // below) pull values out of one.
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("range"),
+        Literal::Callable(Box::new(NativeFunction::new("range", Arity::Range(2, 3), range))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("iter"),
+        Literal::Callable(Box::new(NativeFunction::new("iter", 1, iter))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("filter_iter"),
+        Literal::Callable(Box::new(NativeFunction::new("filter_iter", 2, filter_iter))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("map_iter"),
+        Literal::Callable(Box::new(NativeFunction::new("map_iter", 2, map_iter))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("take"),
+        Literal::Callable(Box::new(NativeFunction::new("take", 2, take))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("zip"),
+        Literal::Callable(Box::new(NativeFunction::new("zip", 2, zip))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("enumerate"),
+        Literal::Callable(Box::new(NativeFunction::new("enumerate", 1, enumerate))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("reduce"),
+        Literal::Callable(Box::new(NativeFunction::new("reduce", 3, reduce))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("fold"),
+        Literal::Callable(Box::new(NativeFunction::new("fold", 3, fold))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("for_each"),
+        Literal::Callable(Box::new(NativeFunction::new("for_each", 2, for_each))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("collect"),
+        Literal::Callable(Box::new(NativeFunction::new("collect", 1, collect))),
+    );
+
+    // Eager array utilities, unlike the lazy combinators above. `find`/
+    // `any`/`all` accept either an array or an iterator (via `as_iterator`)
+    // and return an element (or `nil`) or a boolean; the rest take arrays
+    // only and return a new array.
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("filter"),
+        Literal::Callable(Box::new(NativeFunction::new("filter", 2, filter))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("find"),
+        Literal::Callable(Box::new(NativeFunction::new("find", 2, find))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("any"),
+        Literal::Callable(Box::new(NativeFunction::new("any", 2, any))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("all"),
+        Literal::Callable(Box::new(NativeFunction::new("all", 2, all))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("sort"),
+        Literal::Callable(Box::new(NativeFunction::new("sort", Arity::Range(1, 2), sort))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("reverse"),
+        Literal::Callable(Box::new(NativeFunction::new("reverse", 1, reverse))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("slice"),
+        Literal::Callable(Box::new(NativeFunction::new("slice", 3, slice))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("concat"),
+        Literal::Callable(Box::new(NativeFunction::new("concat", 2, concat))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("flatten"),
+        Literal::Callable(Box::new(NativeFunction::new("flatten", 1, flatten))),
+    );
+
     // Map functions
     interpreter.globals().borrow_mut().define(
-        "Map".to_string(),
+        crate::interner::intern("Map"),
         Literal::Callable(Box::new(NativeFunction::new("Map", 0, map_new))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_has".to_string(),
+        crate::interner::intern("map_has"),
         Literal::Callable(Box::new(NativeFunction::new("map_has", 2, map_has))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_get".to_string(),
+        crate::interner::intern("map_get"),
         Literal::Callable(Box::new(NativeFunction::new("map_get", 2, map_get))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_set".to_string(),
+        crate::interner::intern("map_set"),
         Literal::Callable(Box::new(NativeFunction::new("map_set", 3, map_set))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_remove".to_string(),
+        crate::interner::intern("map_remove"),
         Literal::Callable(Box::new(NativeFunction::new("map_remove", 2, map_remove))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_keys".to_string(),
+        crate::interner::intern("map_keys"),
         Literal::Callable(Box::new(NativeFunction::new("map_keys", 1, map_keys))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_values".to_string(),
+        crate::interner::intern("map_values"),
         Literal::Callable(Box::new(NativeFunction::new("map_values", 1, map_values))),
     );
     
     interpreter.globals().borrow_mut().define(
-        "map_entries".to_string(),
+        crate::interner::intern("map_entries"),
         Literal::Callable(Box::new(NativeFunction::new("map_entries", 1, map_entries))),
     );
+
+    // Numeric tower: exact rationals and complex numbers, promoted between
+    // by arithmetic (see `Literal::numeric_add` and friends) and by `abs`/
+    // `sqrt`/`pow` below.
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("rational"),
+        Literal::Callable(Box::new(NativeFunction::new("rational", 2, rational))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("complex"),
+        Literal::Callable(Box::new(NativeFunction::new("complex", 2, complex))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("numerator"),
+        Literal::Callable(Box::new(NativeFunction::new("numerator", 1, numerator))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("denominator"),
+        Literal::Callable(Box::new(NativeFunction::new("denominator", 1, denominator))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("real"),
+        Literal::Callable(Box::new(NativeFunction::new("real", 1, real))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("imag"),
+        Literal::Callable(Box::new(NativeFunction::new("imag", 1, imag))),
+    );
+
+    // Memory functions
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("arena"),
+        Literal::Callable(Box::new(NativeFunction::new("arena", 0, arena))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("arena_reset"),
+        Literal::Callable(Box::new(NativeFunction::new("arena_reset", 1, arena_reset))),
+    );
+
+    // File and process environment functions
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("read_file"),
+        Literal::Callable(Box::new(NativeFunction::new("read_file", 1, read_file))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("write_file"),
+        Literal::Callable(Box::new(NativeFunction::new("write_file", 2, write_file))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("append_file"),
+        Literal::Callable(Box::new(NativeFunction::new("append_file", 2, append_file))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("read_lines"),
+        Literal::Callable(Box::new(NativeFunction::new("read_lines", 1, read_lines))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("file_exists"),
+        Literal::Callable(Box::new(NativeFunction::new("file_exists", 1, file_exists))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("delete_file"),
+        Literal::Callable(Box::new(NativeFunction::new("delete_file", 1, delete_file))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("env"),
+        Literal::Callable(Box::new(NativeFunction::new("env", 1, env))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("args"),
+        Literal::Callable(Box::new(NativeFunction::new("args", 0, args))),
+    );
+
+    interpreter.globals().borrow_mut().define(
+        crate::interner::intern("exit"),
+        Literal::Callable(Box::new(NativeFunction::new("exit", 1, exit))),
+    );
 }
 
 /// Prints all arguments to stdout, separated by spaces and followed by a newline.
-fn print(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
-    let output: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+fn print(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let mut output = Vec::with_capacity(args.len());
+    for arg in &args {
+        output.push(display(interpreter, arg)?);
+    }
     println!("{}", output.join(" "));
     Ok(Literal::Nil)
 }
 
+/// Renders a value as a string, dispatching to the value's `to_string`
+/// method if it's an instance whose class defines one, otherwise falling
+/// back to `Literal`'s own `Display` impl.
+///
+/// `pub(crate)` so `Stmt::Print` can reuse the same dispatch instead of
+/// printing `Literal`'s raw `Display` impl directly.
+pub(crate) fn display(interpreter: &mut Interpreter, value: &Literal) -> Result<String> {
+    if let Literal::Instance(instance) = value {
+        let bound = instance.borrow().find_magic_method(value, "to_string");
+        if let Some(method) = bound {
+            // Literal's Display quotes strings (so they nest correctly
+            // inside array/map rendering); unwrap that here so a
+            // user-defined `to_string` returning a string prints its
+            // contents verbatim instead of wrapped in literal quotes.
+            return Ok(match method.call(interpreter, vec![])? {
+                Literal::String(s) => s,
+                other => other.to_string(),
+            });
+        }
+    }
+    Ok(value.to_string())
+}
+
 /// Reads a line from stdin and returns it as a string.
 fn input(_: &mut Interpreter, _: Vec<Literal>) -> Result<Literal> {
     let mut input = String::new();
@@ -163,9 +479,68 @@ fn time(_: &mut Interpreter, _: Vec<Literal>) -> Result<Literal> {
     Ok(Literal::Number(since_the_epoch.as_secs_f64()))
 }
 
-/// Converts a value to a string.
-fn to_string(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
-    Ok(Literal::String(args[0].to_string()))
+/// Returns a uniform random `Number` in `[0, 1)`.
+fn random(interpreter: &mut Interpreter, _: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::Number(interpreter.rng().next_f64()))
+}
+
+/// Returns a uniform random integer in `[lo, hi]`, inclusive.
+fn random_int(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let lo = match &args[0] {
+        Literal::Number(n) => *n as i64,
+        _ => return Err(crate::error::general_error("random_int() first argument must be a number")),
+    };
+    let hi = match &args[1] {
+        Literal::Number(n) => *n as i64,
+        _ => return Err(crate::error::general_error("random_int() second argument must be a number")),
+    };
+    if hi < lo {
+        return Err(crate::error::general_error("random_int() requires lo <= hi"));
+    }
+    Ok(Literal::Number(interpreter.rng().next_range(lo, hi) as f64))
+}
+
+/// Picks one element from an array at random.
+fn random_choice(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let elements = match &args[0] {
+        Literal::Array(elements) => elements,
+        _ => return Err(crate::error::general_error("random_choice() argument must be an array")),
+    };
+    if elements.is_empty() {
+        return Err(crate::error::general_error("random_choice() argument must not be empty"));
+    }
+    let index = interpreter.rng().next_range(0, elements.len() as i64 - 1) as usize;
+    Ok(elements[index].clone())
+}
+
+/// Returns a shuffled copy of an array, via Fisher-Yates.
+fn shuffle(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let mut elements = match &args[0] {
+        Literal::Array(elements) => elements.clone(),
+        _ => return Err(crate::error::general_error("shuffle() argument must be an array")),
+    };
+    for i in (1..elements.len()).rev() {
+        let j = interpreter.rng().next_range(0, i as i64) as usize;
+        elements.swap(i, j);
+    }
+    Ok(Literal::Array(elements))
+}
+
+/// Reseeds the random number generator, making subsequent `random`/
+/// `random_int`/`random_choice`/`shuffle` calls reproducible.
+fn seed(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let n = match &args[0] {
+        Literal::Number(n) => *n as u64,
+        _ => return Err(crate::error::general_error("seed() argument must be a number")),
+    };
+    *interpreter.rng() = crate::interpreter::Rng::from_seed(n);
+    Ok(Literal::Nil)
+}
+
+/// Converts a value to a string, dispatching to a user-defined `to_string`
+/// method for instances (see `display`).
+fn to_string(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::String(display(interpreter, &args[0])?))
 }
 
 /// Converts a value to a number.
@@ -180,6 +555,8 @@ fn to_number(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
         }
         Literal::Boolean(true) => Ok(Literal::Number(1.0)),
         Literal::Boolean(false) | Literal::Nil => Ok(Literal::Number(0.0)),
+        Literal::Rational(num, den) => Literal::rational(*num, *den),
+        Literal::Complex(re, im) => Ok(Literal::Complex(*re, *im)),
         _ => Err(crate::error::general_error("Cannot convert value to number")),
     }
 }
@@ -189,43 +566,141 @@ fn to_bool(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     Ok(Literal::Boolean(args[0].is_truthy()))
 }
 
-/// Returns the absolute value of a number.
+/// Returns the absolute value of a number. Rationals keep their exactness;
+/// a complex value's "absolute value" is its modulus `hypot(re, im)`.
 fn abs(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     match &args[0] {
         Literal::Number(n) => Ok(Literal::Number(n.abs())),
+        Literal::Rational(num, den) => Literal::rational(num.abs(), *den),
+        Literal::Complex(re, im) => Ok(Literal::Number(re.hypot(*im))),
         _ => Err(crate::error::general_error("abs() argument must be a number")),
     }
 }
 
-/// Returns the square root of a number.
+/// Returns the square root of a number. A negative `Number`/`Rational`
+/// produces a `Complex` result instead of erroring; `sqrt()` of a `Complex`
+/// returns its principal square root.
 fn sqrt(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     match &args[0] {
-        Literal::Number(n) => {
-            if *n < 0.0 {
-                return Err(crate::error::general_error("sqrt() of negative number"));
-            }
-            Ok(Literal::Number(n.sqrt()))
+        Literal::Number(n) => Ok(sqrt_real(*n)),
+        Literal::Rational(num, den) => Ok(sqrt_real(*num as f64 / *den as f64)),
+        Literal::Complex(re, im) => {
+            let r = re.hypot(*im);
+            let sign = if *im < 0.0 { -1.0 } else { 1.0 };
+            Ok(Literal::Complex(
+                ((r + re) / 2.0).sqrt(),
+                sign * ((r - re) / 2.0).sqrt(),
+            ))
         }
         _ => Err(crate::error::general_error("sqrt() argument must be a number")),
     }
 }
 
+/// Real square root helper shared by the `Number` and `Rational` cases of
+/// `sqrt()`: negative inputs promote to a purely imaginary `Complex`.
+fn sqrt_real(n: f64) -> Literal {
+    if n < 0.0 {
+        Literal::Complex(0.0, (-n).sqrt())
+    } else {
+        Literal::Number(n.sqrt())
+    }
+}
+
 /// Returns the first argument raised to the power of the second argument.
+/// A `Rational` base raised to an integer `Number` exponent stays exact;
+/// a `Complex` base is handled via its polar form, `r^n * (cos(nθ) + i·sin(nθ))`.
 fn pow(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     match (&args[0], &args[1]) {
         (Literal::Number(base), Literal::Number(exp)) => {
             Ok(Literal::Number(base.powf(*exp)))
         }
+        (Literal::Rational(num, den), Literal::Number(exp)) if exp.fract() == 0.0 => {
+            let n = *exp as i32;
+            if n >= 0 {
+                Literal::rational(num.pow(n as u32), den.pow(n as u32))
+            } else {
+                Literal::rational(den.pow((-n) as u32), num.pow((-n) as u32))
+            }
+        }
+        (Literal::Rational(num, den), Literal::Number(exp)) => {
+            Ok(Literal::Number((*num as f64 / *den as f64).powf(*exp)))
+        }
+        (Literal::Complex(re, im), Literal::Number(exp)) => {
+            let r = re.hypot(*im).powf(*exp);
+            let theta = im.atan2(*re) * exp;
+            Ok(Literal::Complex(r * theta.cos(), r * theta.sin()))
+        }
         _ => Err(crate::error::general_error(
             "pow() arguments must be numbers",
         )),
     }
 }
 
+/// Builds a rational literal in lowest terms from a numerator and
+/// denominator, each given as a `Number` (truncated to an integer).
+fn rational(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let num = match &args[0] {
+        Literal::Number(n) => *n as i64,
+        _ => return Err(crate::error::general_error("rational() first argument must be a number")),
+    };
+    let den = match &args[1] {
+        Literal::Number(n) => *n as i64,
+        _ => return Err(crate::error::general_error("rational() second argument must be a number")),
+    };
+    Literal::rational(num, den)
+}
+
+/// Builds a complex literal from a real and imaginary part.
+fn complex(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let re = match &args[0] {
+        Literal::Number(n) => *n,
+        _ => return Err(crate::error::general_error("complex() first argument must be a number")),
+    };
+    let im = match &args[1] {
+        Literal::Number(n) => *n,
+        _ => return Err(crate::error::general_error("complex() second argument must be a number")),
+    };
+    Ok(Literal::Complex(re, im))
+}
+
+/// Returns the numerator of a rational value.
+fn numerator(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    match &args[0] {
+        Literal::Rational(num, _) => Ok(Literal::Number(*num as f64)),
+        _ => Err(crate::error::general_error("numerator() argument must be a rational")),
+    }
+}
+
+/// Returns the denominator of a rational value.
+fn denominator(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    match &args[0] {
+        Literal::Rational(_, den) => Ok(Literal::Number(*den as f64)),
+        _ => Err(crate::error::general_error("denominator() argument must be a rational")),
+    }
+}
+
+/// Returns the real part of a complex value (or a number, unchanged).
+fn real(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    match &args[0] {
+        Literal::Complex(re, _) => Ok(Literal::Number(*re)),
+        Literal::Number(n) => Ok(Literal::Number(*n)),
+        _ => Err(crate::error::general_error("real() argument must be a number")),
+    }
+}
+
+/// Returns the imaginary part of a complex value (zero for a plain number).
+fn imag(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    match &args[0] {
+        Literal::Complex(_, im) => Ok(Literal::Number(*im)),
+        Literal::Number(_) => Ok(Literal::Number(0.0)),
+        _ => Err(crate::error::general_error("imag() argument must be a number")),
+    }
+}
+
 /// Returns the length of a string or array.
 fn len(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     match &args[0] {
-        Literal::String(s) => Ok(Literal::Number(s.len() as f64)),
+        Literal::String(s) => Ok(Literal::Number(s.chars().count() as f64)),
         Literal::Array(elements) => Ok(Literal::Number(elements.len() as f64)),
         _ => Err(crate::error::general_error("len() argument must be a string or array")),
     }
@@ -248,7 +723,7 @@ fn substring(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
         _ => return Err(crate::error::general_error("substring() third argument must be a number")),
     };
     
-    if start > end || end > s.len() {
+    if start > end || end > s.chars().count() {
         return Err(crate::error::general_error("substring() indices out of range"));
     }
     
@@ -256,55 +731,616 @@ fn substring(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     Ok(Literal::String(result))
 }
 
-/// Creates a new array with the given elements.
-fn array(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
-    Ok(Literal::Array(args))
+/// Extracts a `&str` argument, rejecting anything that isn't a `String`.
+fn expect_str<'a>(value: &'a Literal, fn_name: &str) -> Result<&'a str> {
+    match value {
+        Literal::String(s) => Ok(s),
+        _ => Err(crate::error::general_error(&format!(
+            "{}() argument must be a string",
+            fn_name
+        ))),
+    }
 }
 
-/// Adds an element to the end of an array.
-fn push(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
-    let mut array = match &args[0] {
-        Literal::Array(elements) => elements.clone(),
-        _ => return Err(crate::error::general_error("push() first argument must be an array")),
-    };
-    
-    array.push(args[1].clone());
-    Ok(Literal::Array(array))
+/// Splits a string on a separator, returning an array of the pieces.
+fn split(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "split")?;
+    let sep = expect_str(&args[1], "split")?;
+    let pieces = s.split(sep).map(|piece| Literal::String(piece.to_string())).collect();
+    Ok(Literal::Array(pieces))
 }
 
-/// Removes and returns the last element of an array.
-fn pop(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
-    let mut array = match &args[0] {
-        Literal::Array(elements) => elements.clone(),
-        _ => return Err(crate::error::general_error("pop() argument must be an array")),
+/// Joins an array of strings with a separator.
+fn join(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let elements = match &args[0] {
+        Literal::Array(elements) => elements,
+        _ => return Err(crate::error::general_error("join() first argument must be an array")),
     };
-    
-    if array.is_empty() {
-        return Ok(Literal::Nil);
-    }
-    
-    let last = array.pop().unwrap();
-    Ok(last)
+    let sep = expect_str(&args[1], "join")?;
+    let pieces: Result<Vec<&str>> = elements
+        .iter()
+        .map(|element| expect_str(element, "join"))
+        .collect();
+    Ok(Literal::String(pieces?.join(sep)))
 }
 
-/// Applies a function to each element of an array and returns a new array with the results.
-fn map(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
-    let array = match &args[0] {
-        Literal::Array(elements) => elements,
-        _ => return Err(crate::error::general_error("map() first argument must be an array")),
-    };
-    
-    let func = match &args[1] {
-        Literal::Callable(func) => func,
-        _ => return Err(crate::error::general_error("map() second argument must be a function")),
+/// Replaces every occurrence of a substring with another.
+fn replace(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "replace")?;
+    let from = expect_str(&args[1], "replace")?;
+    let to = expect_str(&args[2], "replace")?;
+    Ok(Literal::String(s.replace(from, to)))
+}
+
+/// Converts a string to uppercase.
+fn to_upper(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::String(expect_str(&args[0], "to_upper")?.to_uppercase()))
+}
+
+/// Converts a string to lowercase.
+fn to_lower(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::String(expect_str(&args[0], "to_lower")?.to_lowercase()))
+}
+
+/// Trims leading and trailing whitespace from a string.
+fn trim(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::String(expect_str(&args[0], "trim")?.trim().to_string()))
+}
+
+/// Returns whether a string starts with a prefix.
+fn starts_with(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "starts_with")?;
+    let prefix = expect_str(&args[1], "starts_with")?;
+    Ok(Literal::Boolean(s.starts_with(prefix)))
+}
+
+/// Returns whether a string ends with a suffix.
+fn ends_with(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "ends_with")?;
+    let suffix = expect_str(&args[1], "ends_with")?;
+    Ok(Literal::Boolean(s.ends_with(suffix)))
+}
+
+/// Returns the character index of the first occurrence of a substring, or
+/// -1 if it isn't found. The index counts Unicode scalar values, matching
+/// `len()`/`substring()`, so it can be passed straight back into `substring`.
+fn index_of(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "index_of")?;
+    let sub = expect_str(&args[1], "index_of")?;
+    match s.find(sub) {
+        Some(byte_index) => Ok(Literal::Number(s[..byte_index].chars().count() as f64)),
+        None => Ok(Literal::Number(-1.0)),
+    }
+}
+
+/// Returns whether a string contains a substring.
+fn contains(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "contains")?;
+    let sub = expect_str(&args[1], "contains")?;
+    Ok(Literal::Boolean(s.contains(sub)))
+}
+
+/// Repeats a string `n` times.
+fn repeat(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "repeat")?;
+    let n = match &args[1] {
+        Literal::Number(n) => *n as usize,
+        _ => return Err(crate::error::general_error("repeat() second argument must be a number")),
+    };
+    Ok(Literal::String(s.repeat(n)))
+}
+
+/// Converts a Unicode codepoint to a single-character string.
+fn chr(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let n = match &args[0] {
+        Literal::Number(n) => *n as u32,
+        _ => return Err(crate::error::general_error("chr() argument must be a number")),
+    };
+    let c = char::from_u32(n).ok_or_else(|| {
+        crate::error::general_error(&format!("{} is not a valid Unicode codepoint", n))
+    })?;
+    Ok(Literal::String(c.to_string()))
+}
+
+/// Returns the Unicode codepoint of a string's first character.
+fn ord(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let s = expect_str(&args[0], "ord")?;
+    let c = s.chars().next().ok_or_else(|| {
+        crate::error::general_error("ord() argument must be a non-empty string")
+    })?;
+    Ok(Literal::Number(c as u32 as f64))
+}
+
+/// Creates a new array with the given elements.
+fn array(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::Array(args))
+}
+
+/// Adds an element to the end of an array.
+fn push(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let mut array = match &args[0] {
+        Literal::Array(elements) => elements.clone(),
+        _ => return Err(crate::error::general_error("push() first argument must be an array")),
     };
     
-    let mut result = Vec::new();
-    for (_i, item) in array.iter().enumerate() {
-        let mapped = func.call(interpreter, vec![item.clone()])?;
-        result.push(mapped);
+    array.push(args[1].clone());
+    Ok(Literal::Array(array))
+}
+
+/// Removes and returns the last element of an array.
+fn pop(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let mut array = match &args[0] {
+        Literal::Array(elements) => elements.clone(),
+        _ => return Err(crate::error::general_error("pop() argument must be an array")),
+    };
+    
+    if array.is_empty() {
+        return Ok(Literal::Nil);
     }
     
+    let last = array.pop().unwrap();
+    Ok(last)
+}
+
+/// Applies a function to each element of an array and returns a new array
+/// with the results. Kept for backward compatibility; internally this is
+/// just `collect(map_iter(iter(array), func))`.
+fn map(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    if !matches!(&args[0], Literal::Array(_)) {
+        return Err(crate::error::general_error("map() first argument must be an array"));
+    }
+    if !matches!(&args[1], Literal::Callable(_)) {
+        return Err(crate::error::general_error("map() second argument must be a function"));
+    }
+
+    let mapped = map_iter(interpreter, args)?;
+    collect(interpreter, vec![mapped])
+}
+
+/// Coerces a value into a `LazyIter`: `Literal::Iterator`s pass through
+/// unchanged, and `Literal::Array`s are wrapped in a producer that yields
+/// their elements in order. Used by every combinator/reducer below so they
+/// accept either an explicit `iter(...)` result or a plain array.
+fn as_iterator(value: &Literal, fn_name: &str) -> Result<LazyIter> {
+    match value {
+        Literal::Iterator(iter) => Ok(iter.clone()),
+        Literal::Array(elements) => {
+            let elements = elements.clone();
+            let mut index = 0;
+            Ok(LazyIter::new(move |_interpreter| {
+                if index < elements.len() {
+                    let item = elements[index].clone();
+                    index += 1;
+                    Ok(Some(item))
+                } else {
+                    Ok(None)
+                }
+            }))
+        }
+        _ => Err(crate::error::general_error(&format!(
+            "{}() argument must be an iterator or array",
+            fn_name
+        ))),
+    }
+}
+
+/// Produces a lazy iterator counting from `start` up to (but not including)
+/// `end`, advancing by `step` (default `1`; may be negative to count down).
+fn range(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let start = match &args[0] {
+        Literal::Number(n) => *n,
+        _ => return Err(crate::error::general_error("range() first argument must be a number")),
+    };
+    let end = match &args[1] {
+        Literal::Number(n) => *n,
+        _ => return Err(crate::error::general_error("range() second argument must be a number")),
+    };
+    let step = match args.get(2) {
+        Some(Literal::Number(n)) => *n,
+        Some(_) => return Err(crate::error::general_error("range() third argument must be a number")),
+        None => 1.0,
+    };
+    if step == 0.0 {
+        return Err(crate::error::general_error("range() step must not be zero"));
+    }
+
+    let mut current = start;
+    Ok(Literal::Iterator(LazyIter::new(move |_interpreter| {
+        let exhausted = if step > 0.0 { current >= end } else { current <= end };
+        if exhausted {
+            return Ok(None);
+        }
+        let value = Literal::Number(current);
+        current += step;
+        Ok(Some(value))
+    })))
+}
+
+/// Wraps an array as a lazy iterator over its elements.
+fn iter(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    as_iterator(&args[0], "iter").map(Literal::Iterator)
+}
+
+/// Returns a lazy iterator yielding only the source's items for which `fn`
+/// returns truthy.
+fn filter_iter(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "filter_iter")?;
+    let predicate = match &args[1] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("filter_iter() second argument must be a function")),
+    };
+
+    Ok(Literal::Iterator(LazyIter::new(move |interpreter| {
+        loop {
+            match source.next(interpreter)? {
+                Some(item) => {
+                    if predicate.call(interpreter, vec![item.clone()])?.is_truthy() {
+                        return Ok(Some(item));
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+    })))
+}
+
+/// Returns a lazy iterator yielding `fn(item)` for each item of the source.
+fn map_iter(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "map_iter")?;
+    let func = match &args[1] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("map_iter() second argument must be a function")),
+    };
+
+    Ok(Literal::Iterator(LazyIter::new(move |interpreter| {
+        match source.next(interpreter)? {
+            Some(item) => Ok(Some(func.call(interpreter, vec![item])?)),
+            None => Ok(None),
+        }
+    })))
+}
+
+/// Returns a lazy iterator yielding at most the first `n` items of the
+/// source, never pulling from the source again once `n` have been taken.
+fn take(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "take")?;
+    let limit = match &args[1] {
+        Literal::Number(n) => *n as usize,
+        _ => return Err(crate::error::general_error("take() second argument must be a number")),
+    };
+
+    let mut taken = 0;
+    Ok(Literal::Iterator(LazyIter::new(move |interpreter| {
+        if taken >= limit {
+            return Ok(None);
+        }
+        match source.next(interpreter)? {
+            Some(item) => {
+                taken += 1;
+                Ok(Some(item))
+            }
+            None => {
+                taken = limit;
+                Ok(None)
+            }
+        }
+    })))
+}
+
+/// Returns a lazy iterator of `[a_item, b_item]` pairs, stopping as soon as
+/// either source is exhausted.
+fn zip(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let left = as_iterator(&args[0], "zip")?;
+    let right = as_iterator(&args[1], "zip")?;
+
+    Ok(Literal::Iterator(LazyIter::new(move |interpreter| {
+        match (left.next(interpreter)?, right.next(interpreter)?) {
+            (Some(a), Some(b)) => Ok(Some(Literal::Array(vec![a, b]))),
+            _ => Ok(None),
+        }
+    })))
+}
+
+/// Returns a lazy iterator of `[index, item]` pairs, indices starting at 0.
+fn enumerate(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "enumerate")?;
+    let mut index: f64 = 0.0;
+
+    Ok(Literal::Iterator(LazyIter::new(move |interpreter| {
+        match source.next(interpreter)? {
+            Some(item) => {
+                let pair = Literal::Array(vec![Literal::Number(index), item]);
+                index += 1.0;
+                Ok(Some(pair))
+            }
+            None => Ok(None),
+        }
+    })))
+}
+
+/// Drains the source iterator, folding it down to a single value by calling
+/// `fn(accumulator, item)` starting from `init`.
+fn reduce(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "reduce")?;
+    let mut accumulator = args[1].clone();
+    let func = match &args[2] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("reduce() third argument must be a function")),
+    };
+
+    while let Some(item) = source.next(interpreter)? {
+        accumulator = func.call(interpreter, vec![accumulator, item])?;
+    }
+    Ok(accumulator)
+}
+
+/// Drains the source iterator the same way `reduce` does; the two names are
+/// kept as synonyms since both spellings are common across languages.
+fn fold(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    reduce(interpreter, args)
+}
+
+/// Drains the source iterator, calling `fn(item)` for its side effects and
+/// discarding the results.
+fn for_each(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "for_each")?;
+    let func = match &args[1] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("for_each() second argument must be a function")),
+    };
+
+    while let Some(item) = source.next(interpreter)? {
+        func.call(interpreter, vec![item])?;
+    }
+    Ok(Literal::Nil)
+}
+
+/// Drains the source iterator into a new array, in order.
+fn collect(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "collect")?;
+    let mut result = Vec::new();
+    while let Some(item) = source.next(interpreter)? {
+        result.push(item);
+    }
+    Ok(Literal::Array(result))
+}
+
+/// Returns a new array containing only the elements for which `predicate`
+/// returns truthy. Mirrors `map`/`map_iter`: eagerly collects the output of
+/// the lazy `filter_iter` combinator.
+fn filter(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    if !matches!(&args[0], Literal::Array(_)) {
+        return Err(crate::error::general_error("filter() first argument must be an array"));
+    }
+    if !matches!(&args[1], Literal::Callable(_)) {
+        return Err(crate::error::general_error("filter() second argument must be a function"));
+    }
+
+    let filtered = filter_iter(interpreter, args)?;
+    collect(interpreter, vec![filtered])
+}
+
+/// Returns the first element for which `predicate` returns truthy, or `nil`
+/// if none does.
+fn find(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "find")?;
+    let predicate = match &args[1] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("find() second argument must be a function")),
+    };
+
+    while let Some(item) = source.next(interpreter)? {
+        if predicate.call(interpreter, vec![item.clone()])?.is_truthy() {
+            return Ok(item);
+        }
+    }
+    Ok(Literal::Nil)
+}
+
+/// Returns `true` if `predicate` returns truthy for at least one element.
+fn any(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "any")?;
+    let predicate = match &args[1] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("any() second argument must be a function")),
+    };
+
+    while let Some(item) = source.next(interpreter)? {
+        if predicate.call(interpreter, vec![item])?.is_truthy() {
+            return Ok(Literal::Boolean(true));
+        }
+    }
+    Ok(Literal::Boolean(false))
+}
+
+/// Returns `true` if `predicate` returns truthy for every element (vacuously
+/// `true` for an empty source).
+fn all(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let source = as_iterator(&args[0], "all")?;
+    let predicate = match &args[1] {
+        Literal::Callable(func) => func.clone(),
+        _ => return Err(crate::error::general_error("all() second argument must be a function")),
+    };
+
+    while let Some(item) = source.next(interpreter)? {
+        if !predicate.call(interpreter, vec![item])?.is_truthy() {
+            return Ok(Literal::Boolean(false));
+        }
+    }
+    Ok(Literal::Boolean(true))
+}
+
+/// Compares two values with no comparator supplied: numbers compare
+/// numerically and strings lexicographically, mirroring the types the `>`/
+/// `<` operators already support directly.
+fn default_compare(a: &Literal, b: &Literal) -> Result<Ordering> {
+    match (a, b) {
+        (Literal::Number(x), Literal::Number(y)) => {
+            Ok(x.partial_cmp(y).unwrap_or(Ordering::Equal))
+        }
+        (Literal::String(x), Literal::String(y)) => Ok(x.cmp(y)),
+        _ => Err(crate::error::general_error(
+            "sort() cannot compare these values; pass a comparator",
+        )),
+    }
+}
+
+/// Orders `a` relative to `b`, either via `comparator` (a Demon callable
+/// returning a negative/zero/positive number) or, absent one, via
+/// `default_compare`.
+fn compare_with(
+    interpreter: &mut Interpreter,
+    comparator: &Option<Box<dyn Callable>>,
+    a: &Literal,
+    b: &Literal,
+) -> Result<Ordering> {
+    match comparator {
+        Some(func) => match func.call(interpreter, vec![a.clone(), b.clone()])? {
+            Literal::Number(n) if n < 0.0 => Ok(Ordering::Less),
+            Literal::Number(n) if n > 0.0 => Ok(Ordering::Greater),
+            Literal::Number(_) => Ok(Ordering::Equal),
+            _ => Err(crate::error::general_error(
+                "sort() comparator must return a number",
+            )),
+        },
+        None => default_compare(a, b),
+    }
+}
+
+/// Merges two already-sorted runs into one, preferring `left`'s element on
+/// ties so equal elements keep their relative order (stability).
+fn merge(
+    interpreter: &mut Interpreter,
+    left: Vec<Literal>,
+    right: Vec<Literal>,
+    comparator: &Option<Box<dyn Callable>>,
+) -> Result<Vec<Literal>> {
+    let mut result = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    loop {
+        match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) => {
+                if compare_with(interpreter, comparator, l, r)? == Ordering::Greater {
+                    result.push(right.next().unwrap());
+                } else {
+                    result.push(left.next().unwrap());
+                }
+            }
+            (Some(_), None) => result.push(left.next().unwrap()),
+            (None, Some(_)) => result.push(right.next().unwrap()),
+            (None, None) => return Ok(result),
+        }
+    }
+}
+
+/// Sorts `items` with a stable merge sort. Merge sort (rather than a
+/// quicksort/heapsort) is used because `comparator` may error out partway
+/// through and isn't guaranteed to define a total order; a top-down merge
+/// sort still terminates with a stable ordering whatever the comparator
+/// answers, and returns the first comparator error as soon as it occurs.
+fn merge_sort(
+    interpreter: &mut Interpreter,
+    mut items: Vec<Literal>,
+    comparator: &Option<Box<dyn Callable>>,
+) -> Result<Vec<Literal>> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(interpreter, items, comparator)?;
+    let right = merge_sort(interpreter, right, comparator)?;
+    merge(interpreter, left, right, comparator)
+}
+
+/// Returns a new, stably sorted array. Without a `comparator`, numbers sort
+/// numerically and strings lexicographically; with one, it's called as
+/// `comparator(a, b)` and should return a negative, zero, or positive number.
+fn sort(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let items = match &args[0] {
+        Literal::Array(elements) => elements.clone(),
+        _ => return Err(crate::error::general_error("sort() first argument must be an array")),
+    };
+    let comparator = match args.get(1) {
+        Some(Literal::Callable(func)) => Some(func.clone()),
+        Some(_) => return Err(crate::error::general_error("sort() second argument must be a function")),
+        None => None,
+    };
+
+    let sorted = merge_sort(interpreter, items, &comparator)?;
+    Ok(Literal::Array(sorted))
+}
+
+/// Returns a new array with `array`'s elements in reverse order.
+fn reverse(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let mut elements = match &args[0] {
+        Literal::Array(elements) => elements.clone(),
+        _ => return Err(crate::error::general_error("reverse() argument must be an array")),
+    };
+    elements.reverse();
+    Ok(Literal::Array(elements))
+}
+
+/// Returns a new array of `array[start..end]`, clamped to the array's
+/// bounds (negative or out-of-range indices are pinned rather than erroring).
+fn slice(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let elements = match &args[0] {
+        Literal::Array(elements) => elements,
+        _ => return Err(crate::error::general_error("slice() first argument must be an array")),
+    };
+    let start = match &args[1] {
+        Literal::Number(n) => *n as isize,
+        _ => return Err(crate::error::general_error("slice() second argument must be a number")),
+    };
+    let end = match &args[2] {
+        Literal::Number(n) => *n as isize,
+        _ => return Err(crate::error::general_error("slice() third argument must be a number")),
+    };
+
+    let len = elements.len() as isize;
+    let start = start.clamp(0, len) as usize;
+    let end = end.clamp(0, len) as usize;
+
+    if start >= end {
+        return Ok(Literal::Array(Vec::new()));
+    }
+    Ok(Literal::Array(elements[start..end].to_vec()))
+}
+
+/// Returns a new array with `b`'s elements appended after `a`'s.
+fn concat(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let a = match &args[0] {
+        Literal::Array(elements) => elements,
+        _ => return Err(crate::error::general_error("concat() first argument must be an array")),
+    };
+    let b = match &args[1] {
+        Literal::Array(elements) => elements,
+        _ => return Err(crate::error::general_error("concat() second argument must be an array")),
+    };
+
+    let mut result = Vec::with_capacity(a.len() + b.len());
+    result.extend(a.iter().cloned());
+    result.extend(b.iter().cloned());
+    Ok(Literal::Array(result))
+}
+
+/// Splices any directly nested `Literal::Array` elements into the result
+/// one level deep; elements that aren't arrays are copied through as-is.
+fn flatten(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let elements = match &args[0] {
+        Literal::Array(elements) => elements,
+        _ => return Err(crate::error::general_error("flatten() argument must be an array")),
+    };
+
+    let mut result = Vec::new();
+    for element in elements {
+        match element {
+            Literal::Array(nested) => result.extend(nested.iter().cloned()),
+            other => result.push(other.clone()),
+        }
+    }
     Ok(Literal::Array(result))
 }
 
@@ -324,13 +1360,21 @@ fn map_has(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     Ok(Literal::Boolean(map.borrow().contains_key(&key)))
 }
 
-/// Gets a value from a map by key.
-fn map_get(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+/// Gets a value from a map by key, or, for an instance whose class defines
+/// a `get` method, dispatches to it instead of requiring a `Map`.
+fn map_get(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    if let Literal::Instance(instance) = &args[0] {
+        let bound = instance.borrow().find_magic_method(&args[0], "get");
+        if let Some(method) = bound {
+            return method.call(interpreter, vec![args[1].clone()]);
+        }
+    }
+
     let map = match &args[0] {
         Literal::Map(map) => map,
         _ => return Err(crate::error::general_error("map_get() first argument must be a map")),
     };
-    
+
     let key = args[1].to_string();
     match map.borrow().get(&key) {
         Some(value) => Ok(value.clone()),
@@ -338,19 +1382,27 @@ fn map_get(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     }
 }
 
-/// Sets a value in a map by key.
-fn map_set(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+/// Sets a value in a map by key, or, for an instance whose class defines a
+/// `set` method, dispatches to it instead of requiring a `Map`.
+fn map_set(interpreter: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    if let Literal::Instance(instance) = &args[0] {
+        let bound = instance.borrow().find_magic_method(&args[0], "set");
+        if let Some(method) = bound {
+            return method.call(interpreter, vec![args[1].clone(), args[2].clone()]);
+        }
+    }
+
     let map = match &args[0] {
         Literal::Map(map) => map.clone(),
         _ => return Err(crate::error::general_error("map_set() first argument must be a map")),
     };
-    
+
     let key = args[1].to_string();
     let value = args[2].clone();
-    
+
     let mut map_ref = map.borrow_mut();
     map_ref.insert(key, value);
-    
+
     Ok(Literal::Map(map.clone()))
 }
 
@@ -418,6 +1470,130 @@ fn map_entries(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
     Ok(Literal::Array(entries))
 }
 
+/// Creates a fresh bump-allocating arena, for use as the allocator in
+/// `new(allocator) Type()`. Each such call charges the new instance's
+/// footprint against the arena's bump offset; the instance itself is still
+/// an ordinary `Rc`-managed object, so this bounds an allocation budget
+/// rather than physically relocating objects into arena memory.
+fn arena(_: &mut Interpreter, _: Vec<Literal>) -> Result<Literal> {
+    Ok(Literal::Allocator(std::rc::Rc::new(
+        crate::memory::ArenaAllocator::new(),
+    )))
+}
+
+/// Resets an arena's bump offset back to zero, freeing up the budget charged
+/// by prior `new(arena) Type()` calls. Instances already constructed through
+/// the arena keep their fields untouched, since they never lived in its
+/// memory to begin with.
+fn arena_reset(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    match &args[0] {
+        Literal::Allocator(allocator) => {
+            if let Some(arena) = allocator.as_any().downcast_ref::<crate::memory::ArenaAllocator>() {
+                arena.reset();
+                Ok(Literal::Nil)
+            } else {
+                Err(crate::error::general_error(
+                    "arena_reset() argument must be an arena allocator",
+                ))
+            }
+        }
+        _ => Err(crate::error::general_error(
+            "arena_reset() argument must be an allocator",
+        )),
+    }
+}
+
+/// Extracts a path argument, rejecting anything that isn't a `String`.
+fn expect_path<'a>(value: &'a Literal, fn_name: &str) -> Result<&'a str> {
+    match value {
+        Literal::String(path) => Ok(path),
+        _ => Err(crate::error::general_error(&format!(
+            "{}() argument must be a string path",
+            fn_name
+        ))),
+    }
+}
+
+/// Reads an entire file into a string.
+fn read_file(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let path = expect_path(&args[0], "read_file")?;
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| crate::error::general_error(&format!("Failed to read '{}': {}", path, e)))?;
+    Ok(Literal::String(contents))
+}
+
+/// Writes a string to a file, overwriting any existing contents.
+fn write_file(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let path = expect_path(&args[0], "write_file")?;
+    let contents = expect_path(&args[1], "write_file")?;
+    std::fs::write(path, contents)
+        .map_err(|e| crate::error::general_error(&format!("Failed to write '{}': {}", path, e)))?;
+    Ok(Literal::Nil)
+}
+
+/// Appends a string to a file, creating it if it doesn't exist.
+fn append_file(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    use std::io::Write;
+    let path = expect_path(&args[0], "append_file")?;
+    let contents = expect_path(&args[1], "append_file")?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| crate::error::general_error(&format!("Failed to open '{}': {}", path, e)))?;
+    file.write_all(contents.as_bytes())
+        .map_err(|e| crate::error::general_error(&format!("Failed to append to '{}': {}", path, e)))?;
+    Ok(Literal::Nil)
+}
+
+/// Reads a file's lines into an array of strings.
+fn read_lines(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let path = expect_path(&args[0], "read_lines")?;
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| crate::error::general_error(&format!("Failed to read '{}': {}", path, e)))?;
+    let lines = contents.lines().map(|line| Literal::String(line.to_string())).collect();
+    Ok(Literal::Array(lines))
+}
+
+/// Returns whether a file exists at the given path.
+fn file_exists(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let path = expect_path(&args[0], "file_exists")?;
+    Ok(Literal::Boolean(std::path::Path::new(path).exists()))
+}
+
+/// Deletes a file at the given path.
+fn delete_file(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let path = expect_path(&args[0], "delete_file")?;
+    std::fs::remove_file(path)
+        .map_err(|e| crate::error::general_error(&format!("Failed to delete '{}': {}", path, e)))?;
+    Ok(Literal::Nil)
+}
+
+/// Reads an environment variable, returning `nil` if it isn't set.
+fn env(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let name = expect_path(&args[0], "env")?;
+    match std::env::var(name) {
+        Ok(value) => Ok(Literal::String(value)),
+        Err(_) => Ok(Literal::Nil),
+    }
+}
+
+/// Returns the process's command-line arguments (excluding the binary name)
+/// as an array of strings.
+fn args(_: &mut Interpreter, _: Vec<Literal>) -> Result<Literal> {
+    let argv = std::env::args().skip(1).map(Literal::String).collect();
+    Ok(Literal::Array(argv))
+}
+
+/// Terminates the process immediately with the given exit code.
+fn exit(_: &mut Interpreter, args: Vec<Literal>) -> Result<Literal> {
+    let code = match &args[0] {
+        Literal::Number(n) => *n as i32,
+        _ => return Err(crate::error::general_error("exit() argument must be a number")),
+    };
+    std::process::exit(code);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -466,4 +1642,300 @@ mod tests {
             Literal::Number(8.0)
         );
     }
+
+    /// Runs a full Demon script through the real scan/parse/resolve/interpret
+    /// pipeline (with the stdlib registered) and returns the value bound to
+    /// a top-level `result` variable, so dunder-method dispatch (which needs
+    /// an instance of a class declared in Demon source) can be tested
+    /// the same way a user's script would exercise it.
+    fn eval_result(source: &str) -> Result<Literal> {
+        let stmts = crate::parse(source).map_err(|errors| errors.into_iter().next().unwrap())?;
+        let locals = crate::resolver::resolve(&stmts)?;
+        let mut interp = Interpreter::default();
+        register_stdlib(&mut interp);
+        interp.load_resolution(locals);
+        interp.interpret(&stmts)?;
+        let name = crate::lexer::Token::new(
+            crate::lexer::TokenType::Identifier("result".to_string()),
+            "result".to_string(),
+            0,
+        );
+        interp.globals().borrow().get(&name)
+    }
+
+    #[test]
+    fn test_user_defined_to_string_is_dispatched_by_to_string_fn() {
+        let result = eval_result(
+            "class Point { init(x) { this.x = x; } to_string() { return \"Point(\" + to_string(this.x) + \")\"; } } var p = new Point(3); var result = to_string(p);",
+        )
+        .unwrap();
+        assert_eq!(result, Literal::String("Point(3)".to_string()));
+    }
+
+    #[test]
+    fn test_lambda_closes_over_the_variable_resolved_at_declaration_not_by_name() {
+        // A lambda created before an inner `var a` shadows the outer one
+        // must keep pointing at the outer `a` it closed over, per the
+        // resolver's static scope depths -- not fall back to whichever `a`
+        // is nearest by name when it's later called.
+        let result = eval_result(
+            "var a = \"global\"; var f; var result; { f = () -> a; var first = f(); var a = \"block\"; var second = f(); result = first == \"global\" && second == \"global\"; }",
+        )
+        .unwrap();
+        assert_eq!(result, Literal::Boolean(true));
+    }
+
+    #[test]
+    fn test_print_statement_dispatches_user_defined_to_string() {
+        // The `print` statement (unlike a call to the stdlib `to_string(x)`
+        // function) doesn't run through `eval_result`'s top-level `result`
+        // variable, so exercise `display` directly the same way `Stmt::Print`
+        // does, against a value produced by the real interpreter pipeline.
+        let source = "class Point { init(x) { this.x = x; } to_string() { return \"Point(\" + to_string(this.x) + \")\"; } } var result = new Point(3);";
+        let stmts = crate::parse(source).map_err(|errors| errors.into_iter().next().unwrap()).unwrap();
+        let locals = crate::resolver::resolve(&stmts).unwrap();
+        let mut interp = Interpreter::default();
+        register_stdlib(&mut interp);
+        interp.load_resolution(locals);
+        interp.interpret(&stmts).unwrap();
+        let name = crate::lexer::Token::new(
+            crate::lexer::TokenType::Identifier("result".to_string()),
+            "result".to_string(),
+            0,
+        );
+        let point = interp.globals().borrow().get(&name).unwrap();
+        let text = display(&mut interp, &point).unwrap();
+        assert_eq!(text, "Point(3)");
+    }
+
+    #[test]
+    fn test_user_defined_add_operator_is_dispatched() {
+        let result = eval_result(
+            "class Vec { init(n) { this.n = n; } add(other) { return this.n + other.n; } } var a = new Vec(2); var b = new Vec(5); var result = a + b;",
+        )
+        .unwrap();
+        assert_eq!(result, Literal::Number(7.0));
+    }
+
+    #[test]
+    fn test_instance_without_add_method_errors_on_plus() {
+        let result = eval_result(
+            "class Empty { init() {} } var a = new Empty(); var b = new Empty(); var result = a + b;",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_user_defined_equals_operator_is_dispatched() {
+        let result = eval_result(
+            "class Point { init(x) { this.x = x; } equals(other) { return this.x == other.x; } } var a = new Point(1); var b = new Point(1); var result = a == b;",
+        )
+        .unwrap();
+        assert_eq!(result, Literal::Boolean(true));
+    }
+
+    #[test]
+    fn test_user_defined_get_operator_is_dispatched_by_index_access() {
+        let result = eval_result(
+            "class Bag { init() { this.offset = 10; } get(i) { return this.offset + i; } } var b = new Bag(); var result = b[1];",
+        )
+        .unwrap();
+        assert_eq!(result, Literal::Number(11.0));
+    }
+
+    #[test]
+    fn test_user_defined_set_operator_is_dispatched_by_index_assignment() {
+        let result = eval_result(
+            "class Bag { init() { this.last_key = nil; this.last_value = nil; } set(k, v) { this.last_key = k; this.last_value = v; } get(k) { return this.last_value; } } var b = new Bag(); b[\"x\"] = 9; var result = b[\"x\"];",
+        )
+        .unwrap();
+        assert_eq!(result, Literal::Number(9.0));
+    }
+
+    #[test]
+    fn test_instance_without_get_method_errors_on_index_access() {
+        let result = eval_result(
+            "class Empty { init() {} } var e = new Empty(); var result = e[0];",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_custom_new_charges_the_instance_footprint_to_the_arena() {
+        // `new(arena) Point()` doesn't place the instance in arena memory
+        // (see the `CustomNew` doc comment), but it must still charge the
+        // instance's footprint against the arena's bump offset.
+        let source = "var a = arena(); class Point { init() {} } var result = new(a) Point(); var arena_handle = a;";
+        let stmts = crate::parse(source).map_err(|errors| errors.into_iter().next().unwrap()).unwrap();
+        let locals = crate::resolver::resolve(&stmts).unwrap();
+        let mut interp = Interpreter::default();
+        register_stdlib(&mut interp);
+        interp.load_resolution(locals);
+        interp.interpret(&stmts).unwrap();
+        let name = crate::lexer::Token::new(
+            crate::lexer::TokenType::Identifier("arena_handle".to_string()),
+            "arena_handle".to_string(),
+            0,
+        );
+        let arena_handle = interp.globals().borrow().get(&name).unwrap();
+        match arena_handle {
+            Literal::Allocator(allocator) => {
+                let arena = allocator
+                    .as_any()
+                    .downcast_ref::<crate::memory::ArenaAllocator>()
+                    .unwrap();
+                assert!(arena.bytes_used() > 0);
+            }
+            _ => panic!("expected arena() to produce a Literal::Allocator"),
+        }
+    }
+
+    #[test]
+    fn test_substring_out_of_range_errors() {
+        let mut interp = Interpreter::default();
+        let args = vec![
+            Literal::String("hi".to_string()),
+            Literal::Number(0.0),
+            Literal::Number(5.0),
+        ];
+        assert!(substring(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_substring_start_after_end_errors() {
+        let mut interp = Interpreter::default();
+        let args = vec![
+            Literal::String("hello".to_string()),
+            Literal::Number(3.0),
+            Literal::Number(1.0),
+        ];
+        assert!(substring(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_slice_out_of_range_clamps_instead_of_erroring() {
+        let mut interp = Interpreter::default();
+        let args = vec![
+            Literal::Array(vec![Literal::Number(1.0), Literal::Number(2.0)]),
+            Literal::Number(-5.0),
+            Literal::Number(50.0),
+        ];
+        assert_eq!(
+            slice(&mut interp, args).unwrap(),
+            Literal::Array(vec![Literal::Number(1.0), Literal::Number(2.0)])
+        );
+    }
+
+    fn erroring_comparator() -> Literal {
+        fn fail(_: &mut Interpreter, _: Vec<Literal>) -> Result<Literal> {
+            Err(crate::error::general_error("comparator blew up"))
+        }
+        Literal::Callable(Box::new(NativeFunction::new("fail", 2, fail)))
+    }
+
+    #[test]
+    fn test_sort_propagates_comparator_error() {
+        let mut interp = Interpreter::default();
+        let args = vec![
+            Literal::Array(vec![Literal::Number(2.0), Literal::Number(1.0)]),
+            erroring_comparator(),
+        ];
+        assert!(sort(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_sort_without_comparator_sorts_numerically() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::Array(vec![
+            Literal::Number(3.0),
+            Literal::Number(1.0),
+            Literal::Number(2.0),
+        ])];
+        assert_eq!(
+            sort(&mut interp, args).unwrap(),
+            Literal::Array(vec![Literal::Number(1.0), Literal::Number(2.0), Literal::Number(3.0)])
+        );
+    }
+
+    #[test]
+    fn test_range_zero_step_errors() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::Number(0.0), Literal::Number(5.0), Literal::Number(0.0)];
+        assert!(range(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_range_yields_expected_sequence() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::Number(0.0), Literal::Number(3.0)];
+        let iterator = match range(&mut interp, args).unwrap() {
+            Literal::Iterator(it) => it,
+            other => panic!("expected an Iterator, got {:?}", other),
+        };
+        let mut values = Vec::new();
+        while let Some(value) = iterator.next(&mut interp).unwrap() {
+            values.push(value);
+        }
+        assert_eq!(
+            values,
+            vec![Literal::Number(0.0), Literal::Number(1.0), Literal::Number(2.0)]
+        );
+    }
+
+    #[test]
+    fn test_read_file_missing_path_errors() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::String("/nonexistent/path/demon-test.txt".to_string())];
+        assert!(read_file(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_write_file_then_read_file_round_trips() {
+        let mut interp = Interpreter::default();
+        let path = std::env::temp_dir().join("demon_stdlib_test_round_trip.txt");
+        let path = path.to_str().unwrap().to_string();
+
+        write_file(&mut interp, vec![Literal::String(path.clone()), Literal::String("hello".to_string())]).unwrap();
+        assert_eq!(
+            read_file(&mut interp, vec![Literal::String(path.clone())]).unwrap(),
+            Literal::String("hello".to_string())
+        );
+
+        delete_file(&mut interp, vec![Literal::String(path)]).unwrap();
+    }
+
+    #[test]
+    fn test_seed_makes_random_int_reproducible() {
+        let mut interp = Interpreter::default();
+        let args = || vec![Literal::Number(1.0), Literal::Number(100.0)];
+
+        seed(&mut interp, vec![Literal::Number(42.0)]).unwrap();
+        let first = random_int(&mut interp, args()).unwrap();
+
+        seed(&mut interp, vec![Literal::Number(42.0)]).unwrap();
+        let second = random_int(&mut interp, args()).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_random_int_rejects_hi_less_than_lo() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::Number(10.0), Literal::Number(1.0)];
+        assert!(random_int(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_random_choice_rejects_empty_array() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::Array(Vec::new())];
+        assert!(random_choice(&mut interp, args).is_err());
+    }
+
+    #[test]
+    fn test_env_unset_variable_returns_nil() {
+        let mut interp = Interpreter::default();
+        let args = vec![Literal::String("DEMON_STDLIB_TEST_UNSET_VAR".to_string())];
+        assert_eq!(env(&mut interp, args).unwrap(), Literal::Nil);
+    }
 }
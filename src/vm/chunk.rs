@@ -0,0 +1,87 @@
+//! The bytecode container produced by the compiler and executed by the VM.
+
+use crate::lexer::Token;
+
+use super::value::Value;
+
+/// A single bytecode instruction. Operands that index into
+/// `Chunk::constants` or jump elsewhere in `Chunk::code` are stored inline,
+/// rather than through a separate operand table.
+#[derive(Debug, Clone)]
+pub enum OpCode {
+    Constant(usize),
+    Nil,
+    True,
+    False,
+    Pop,
+    GetLocal(usize),
+    SetLocal(usize),
+    GetGlobal(usize),
+    DefineGlobal(usize),
+    SetGlobal(usize),
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Not,
+    Negate,
+    Print,
+    /// Unconditional jump to the instruction at this index.
+    Jump(usize),
+    /// Jumps to the instruction at this index if the top of the stack is
+    /// falsy. Always pops the condition.
+    JumpIfFalse(usize),
+    /// Calls the callable `argc` below the top of the stack with the `argc`
+    /// values above it as arguments.
+    Call(usize),
+    Return,
+}
+
+/// A compiled unit of bytecode: a flat instruction stream, the constant
+/// pool its `Constant`/`*Global` operands index into, and a source token per
+/// instruction so the VM can build a `RuntimeError` with a real span when
+/// something goes wrong.
+#[derive(Debug, Clone, Default)]
+pub struct Chunk {
+    pub code: Vec<OpCode>,
+    pub constants: Vec<Value>,
+    pub tokens: Vec<Token>,
+}
+
+impl Chunk {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an instruction tagged with the token it was compiled from,
+    /// and returns its index. The compiler uses that index to patch jump
+    /// targets once it knows where a branch should land.
+    pub fn emit(&mut self, op: OpCode, token: Token) -> usize {
+        self.code.push(op);
+        self.tokens.push(token);
+        self.code.len() - 1
+    }
+
+    /// Overwrites the instruction at `index`, used to back-patch a
+    /// placeholder jump once its target is known.
+    pub fn patch(&mut self, index: usize, op: OpCode) {
+        self.code[index] = op;
+    }
+
+    /// The index the next `emit` call will be placed at.
+    pub fn next_index(&self) -> usize {
+        self.code.len()
+    }
+
+    /// Adds a value to the constant pool and returns its index.
+    pub fn add_constant(&mut self, value: Value) -> usize {
+        self.constants.push(value);
+        self.constants.len() - 1
+    }
+}
@@ -0,0 +1,576 @@
+//! Compiles a parsed program into the bytecode the VM executes.
+//!
+//! This does its own scope analysis from scratch (tracking locals as stack
+//! slots the way a `clox`-style compiler does) rather than consuming
+//! `resolver::Locals` — the tree-walking `Interpreter` and the VM are two
+//! independent backends over the same AST, and the VM's notion of a
+//! "local" (a stack slot) doesn't correspond to the tree-walker's (an
+//! `Environment` hop count).
+//!
+//! Coverage is narrower than the tree-walker: classes, `new`/`delete`
+//! pointer expressions, arrays/maps, lambdas, and pipelines aren't
+//! compiled yet, and each of those reports a `RuntimeError`/`General` error
+//! that names the unsupported construct. Nested functions can't close over
+//! the enclosing frame's locals (no upvalues), so a local function can't
+//! recurse by name either; no error names that case: a reference to an
+//! enclosing frame's local compiles as a global lookup, which fails with
+//! "Undefined variable" at run time or reads a global of the same name.
+
+use std::rc::Rc;
+
+use crate::error::{Error, Result, RuntimeError};
+use crate::lexer::Token;
+use crate::parser::{Expr, Stmt};
+
+use super::chunk::{Chunk, OpCode};
+use super::value::{Value, VmFunction};
+
+/// A single local variable's slot within the function currently being
+/// compiled. `depth` is the block-nesting depth it was declared at, used to
+/// know which locals fall out of scope (and need a `Pop`) at `end_scope`.
+struct Local {
+    name: String,
+    depth: usize,
+}
+
+/// Per-function compilation state: the chunk being built, and the stack
+/// slots assigned to its locals so far. A new one is pushed for every
+/// function declaration, so nested functions compile into their own
+/// self-contained `Chunk`.
+struct FunctionScope {
+    chunk: Chunk,
+    locals: Vec<Local>,
+    scope_depth: usize,
+}
+
+impl FunctionScope {
+    fn new() -> Self {
+        Self {
+            chunk: Chunk::new(),
+            locals: Vec::new(),
+            scope_depth: 0,
+        }
+    }
+}
+
+/// Tracks the jump patch sites a loop's `break`/`continue` statements need,
+/// plus how many locals were live when the loop body started (so a `break`
+/// or `continue` can pop exactly the locals its jump skips past).
+struct LoopScope {
+    break_jumps: Vec<usize>,
+    continue_jumps: Vec<usize>,
+    locals_at_start: usize,
+}
+
+/// Walks a parsed program and emits bytecode for it.
+pub struct Compiler {
+    scopes: Vec<FunctionScope>,
+    loops: Vec<LoopScope>,
+}
+
+impl Compiler {
+    fn new() -> Self {
+        Self {
+            scopes: vec![FunctionScope::new()],
+            loops: Vec::new(),
+        }
+    }
+
+    /// Compiles a whole program into a zero-argument `VmFunction` named
+    /// `<script>`, the VM's entry point.
+    pub fn compile(statements: &[Stmt]) -> Result<VmFunction> {
+        let mut compiler = Compiler::new();
+        for stmt in statements {
+            compiler.compile_stmt(stmt)?;
+        }
+        let eof = Token::new(crate::lexer::TokenType::Eof, String::new(), 0);
+        compiler.emit(OpCode::Nil, eof.clone());
+        compiler.emit(OpCode::Return, eof);
+
+        let scope = compiler.scopes.pop().expect("script scope always present");
+        Ok(VmFunction {
+            name: "<script>".to_string(),
+            arity: 0,
+            chunk: scope.chunk,
+        })
+    }
+
+    fn scope(&self) -> &FunctionScope {
+        self.scopes.last().expect("at least one function scope")
+    }
+
+    fn scope_mut(&mut self) -> &mut FunctionScope {
+        self.scopes.last_mut().expect("at least one function scope")
+    }
+
+    fn emit(&mut self, op: OpCode, token: Token) -> usize {
+        self.scope_mut().chunk.emit(op, token)
+    }
+
+    /// True while compiling statements directly in the top-level script,
+    /// where variables live in the VM's global table rather than on the
+    /// stack.
+    fn is_global_scope(&self) -> bool {
+        self.scopes.len() == 1 && self.scope().scope_depth == 0
+    }
+
+    fn begin_scope(&mut self) {
+        self.scope_mut().scope_depth += 1;
+    }
+
+    /// Pops the function scope's current block, emitting a `Pop` for every
+    /// local that falls out of scope so the VM's stack stays in sync with
+    /// what the compiler thinks is on it.
+    fn end_scope(&mut self, token: &Token) {
+        self.scope_mut().scope_depth -= 1;
+        let new_depth = self.scope().scope_depth;
+        while self.scope().locals.last().is_some_and(|local| local.depth > new_depth) {
+            self.scope_mut().locals.pop();
+            self.emit(OpCode::Pop, token.clone());
+        }
+    }
+
+    fn identifier_constant(&mut self, name: &str) -> usize {
+        self.scope_mut()
+            .chunk
+            .add_constant(Value::String(Rc::new(name.to_string())))
+    }
+
+    fn resolve_local(&self, name: &str) -> Option<usize> {
+        self.scope()
+            .locals
+            .iter()
+            .rposition(|local| local.name == name)
+    }
+
+    /// Declares `name` as a binding in the current scope: a global constant
+    /// index at the top level, or a new stack-slot local inside a function
+    /// or block. Compile the initializer *before* calling this so a
+    /// variable can't refer to itself the way `var x = x;` would.
+    fn declare_variable(&mut self, name: &Token) -> Option<usize> {
+        if self.is_global_scope() {
+            Some(self.identifier_constant(&name.lexeme))
+        } else {
+            let depth = self.scope().scope_depth;
+            self.scope_mut().locals.push(Local {
+                name: name.lexeme.clone(),
+                depth,
+            });
+            None
+        }
+    }
+
+    fn compile_stmts(&mut self, statements: &[Stmt]) -> Result<()> {
+        for stmt in statements {
+            self.compile_stmt(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn compile_stmt(&mut self, stmt: &Stmt) -> Result<()> {
+        match stmt {
+            Stmt::Empty => Ok(()),
+            Stmt::Expression(expr) => {
+                let token = expr.first_token();
+                self.compile_expr(expr)?;
+                self.emit(OpCode::Pop, token);
+                Ok(())
+            }
+            Stmt::Print(expr) => {
+                let token = expr.first_token();
+                self.compile_expr(expr)?;
+                self.emit(OpCode::Print, token);
+                Ok(())
+            }
+            Stmt::Var { name, initializer } => {
+                match initializer {
+                    Some(expr) => self.compile_expr(expr)?,
+                    None => {
+                        self.emit(OpCode::Nil, name.clone());
+                    }
+                }
+                if let Some(global) = self.declare_variable(name) {
+                    self.emit(OpCode::DefineGlobal(global), name.clone());
+                }
+                Ok(())
+            }
+            Stmt::Const { name, initializer } => {
+                self.compile_expr(initializer)?;
+                if let Some(global) = self.declare_variable(name) {
+                    self.emit(OpCode::DefineGlobal(global), name.clone());
+                }
+                Ok(())
+            }
+            Stmt::Block(statements) => {
+                let token = stmt.first_token().unwrap_or_else(name_eof);
+                self.begin_scope();
+                let result = self.compile_stmts(statements);
+                self.end_scope(&token);
+                result
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let token = condition.first_token();
+                self.compile_expr(condition)?;
+                let then_jump = self.emit(OpCode::JumpIfFalse(0), token.clone());
+                self.compile_stmt(then_branch)?;
+
+                if let Some(else_branch) = else_branch {
+                    let else_jump = self.emit(OpCode::Jump(0), token.clone());
+                    let else_start = self.scope().chunk.next_index();
+                    self.scope_mut().chunk.patch(then_jump, OpCode::JumpIfFalse(else_start));
+                    self.compile_stmt(else_branch)?;
+                    let end = self.scope().chunk.next_index();
+                    self.scope_mut().chunk.patch(else_jump, OpCode::Jump(end));
+                } else {
+                    let end = self.scope().chunk.next_index();
+                    self.scope_mut().chunk.patch(then_jump, OpCode::JumpIfFalse(end));
+                }
+                Ok(())
+            }
+            Stmt::While { condition, body } => {
+                let token = condition.first_token();
+                let loop_start = self.scope().chunk.next_index();
+                self.compile_expr(condition)?;
+                let exit_jump = self.emit(OpCode::JumpIfFalse(0), token.clone());
+
+                self.loops.push(LoopScope {
+                    break_jumps: Vec::new(),
+                    continue_jumps: Vec::new(),
+                    locals_at_start: self.scope().locals.len(),
+                });
+                self.compile_stmt(body)?;
+
+                let continue_target = self.scope().chunk.next_index();
+                self.emit(OpCode::Jump(loop_start), token.clone());
+                let end = self.scope().chunk.next_index();
+                self.scope_mut().chunk.patch(exit_jump, OpCode::JumpIfFalse(end));
+                self.end_loop(continue_target, end);
+                Ok(())
+            }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                let token = body.first_token().unwrap_or_else(name_eof);
+                self.begin_scope();
+                if let Some(init) = initializer {
+                    self.compile_stmt(init)?;
+                }
+
+                let loop_start = self.scope().chunk.next_index();
+                let exit_jump = match condition {
+                    Some(cond) => {
+                        self.compile_expr(cond)?;
+                        Some(self.emit(OpCode::JumpIfFalse(0), cond.first_token()))
+                    }
+                    None => None,
+                };
+
+                self.loops.push(LoopScope {
+                    break_jumps: Vec::new(),
+                    continue_jumps: Vec::new(),
+                    locals_at_start: self.scope().locals.len(),
+                });
+                self.compile_stmt(body)?;
+
+                let continue_target = self.scope().chunk.next_index();
+                if let Some(inc) = increment {
+                    self.compile_expr(inc)?;
+                    self.emit(OpCode::Pop, inc.first_token());
+                }
+                self.emit(OpCode::Jump(loop_start), token.clone());
+
+                let end = self.scope().chunk.next_index();
+                if let Some(exit_jump) = exit_jump {
+                    self.scope_mut().chunk.patch(exit_jump, OpCode::JumpIfFalse(end));
+                }
+                self.end_loop(continue_target, end);
+                self.end_scope(&token);
+                Ok(())
+            }
+            Stmt::Break(keyword) => {
+                let Some(loop_scope) = self.loops.last() else {
+                    return Err(Error::General("Can't use 'break' outside of a loop.".to_string()));
+                };
+                let locals_at_start = loop_scope.locals_at_start;
+                self.pop_loop_locals(locals_at_start, keyword);
+                let jump = self.emit(OpCode::Jump(0), keyword.clone());
+                self.loops.last_mut().unwrap().break_jumps.push(jump);
+                Ok(())
+            }
+            Stmt::Continue(keyword) => {
+                let Some(loop_scope) = self.loops.last() else {
+                    return Err(Error::General("Can't use 'continue' outside of a loop.".to_string()));
+                };
+                let locals_at_start = loop_scope.locals_at_start;
+                self.pop_loop_locals(locals_at_start, keyword);
+                let jump = self.emit(OpCode::Jump(0), keyword.clone());
+                self.loops.last_mut().unwrap().continue_jumps.push(jump);
+                Ok(())
+            }
+            Stmt::Function { name, params, body } => self.compile_function(name, params, body),
+            Stmt::Return { keyword, value } => {
+                match value {
+                    Some(expr) => self.compile_expr(expr)?,
+                    None => {
+                        self.emit(OpCode::Nil, keyword.clone());
+                    }
+                }
+                self.emit(OpCode::Return, keyword.clone());
+                Ok(())
+            }
+            Stmt::Class { name, .. } => Err(RuntimeError::new(
+                name.clone(),
+                "Classes aren't supported by the bytecode VM backend yet.".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Emits a `Pop` for every local declared since the loop body started,
+    /// so a `break`/`continue` jump leaves the stack exactly as it would be
+    /// if control had fallen out of those blocks normally.
+    fn pop_loop_locals(&mut self, locals_at_start: usize, token: &Token) {
+        let extra = self.scope().locals.len().saturating_sub(locals_at_start);
+        for _ in 0..extra {
+            self.emit(OpCode::Pop, token.clone());
+        }
+    }
+
+    fn end_loop(&mut self, continue_target: usize, end: usize) {
+        let loop_scope = self.loops.pop().expect("end_loop without begin");
+        for jump in loop_scope.break_jumps {
+            self.scope_mut().chunk.patch(jump, OpCode::Jump(end));
+        }
+        for jump in loop_scope.continue_jumps {
+            self.scope_mut().chunk.patch(jump, OpCode::Jump(continue_target));
+        }
+    }
+
+    fn compile_function(&mut self, name: &Token, params: &[Token], body: &[Stmt]) -> Result<()> {
+        self.scopes.push(FunctionScope::new());
+        for param in params {
+            self.scope_mut().locals.push(Local {
+                name: param.lexeme.clone(),
+                depth: 0,
+            });
+        }
+        self.compile_stmts(body)?;
+        self.emit(OpCode::Nil, name.clone());
+        self.emit(OpCode::Return, name.clone());
+
+        let scope = self.scopes.pop().expect("function scope pushed above");
+        let function = VmFunction {
+            name: name.lexeme.clone(),
+            arity: params.len(),
+            chunk: scope.chunk,
+        };
+        let constant = self
+            .scope_mut()
+            .chunk
+            .add_constant(Value::Function(Rc::new(function)));
+        self.emit(OpCode::Constant(constant), name.clone());
+
+        if let Some(global) = self.declare_variable(name) {
+            self.emit(OpCode::DefineGlobal(global), name.clone());
+        }
+        Ok(())
+    }
+
+    fn compile_expr(&mut self, expr: &Expr) -> Result<()> {
+        match expr {
+            Expr::Literal(token) => self.compile_literal(token),
+            Expr::Grouping(inner) => self.compile_expr(inner),
+            Expr::Variable(name) => {
+                match self.resolve_local(&name.lexeme) {
+                    Some(slot) => self.emit(OpCode::GetLocal(slot), name.clone()),
+                    None => {
+                        let global = self.identifier_constant(&name.lexeme);
+                        self.emit(OpCode::GetGlobal(global), name.clone())
+                    }
+                };
+                Ok(())
+            }
+            Expr::Assign { name, value } => {
+                self.compile_expr(value)?;
+                match self.resolve_local(&name.lexeme) {
+                    Some(slot) => self.emit(OpCode::SetLocal(slot), name.clone()),
+                    None => {
+                        let global = self.identifier_constant(&name.lexeme);
+                        self.emit(OpCode::SetGlobal(global), name.clone())
+                    }
+                };
+                Ok(())
+            }
+            Expr::Unary { operator, right } => {
+                self.compile_expr(right)?;
+                match operator.token_type {
+                    crate::lexer::TokenType::Minus => {
+                        self.emit(OpCode::Negate, operator.clone());
+                    }
+                    crate::lexer::TokenType::Bang => {
+                        self.emit(OpCode::Not, operator.clone());
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(
+                            operator.clone(),
+                            "Invalid unary operator.".to_string(),
+                        )
+                        .into())
+                    }
+                }
+                Ok(())
+            }
+            Expr::Binary { left, operator, right } => {
+                self.compile_expr(left)?;
+                self.compile_expr(right)?;
+                let op = match operator.token_type {
+                    crate::lexer::TokenType::Plus => OpCode::Add,
+                    crate::lexer::TokenType::Minus => OpCode::Subtract,
+                    crate::lexer::TokenType::Star => OpCode::Multiply,
+                    crate::lexer::TokenType::Slash => OpCode::Divide,
+                    crate::lexer::TokenType::EqualEqual => OpCode::Equal,
+                    crate::lexer::TokenType::BangEqual => OpCode::NotEqual,
+                    crate::lexer::TokenType::Greater => OpCode::Greater,
+                    crate::lexer::TokenType::GreaterEqual => OpCode::GreaterEqual,
+                    crate::lexer::TokenType::Less => OpCode::Less,
+                    crate::lexer::TokenType::LessEqual => OpCode::LessEqual,
+                    _ => {
+                        return Err(RuntimeError::new(
+                            operator.clone(),
+                            "Invalid binary operator.".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                self.emit(op, operator.clone());
+                Ok(())
+            }
+            Expr::Logical { left, operator, right } => {
+                self.compile_expr(left)?;
+                match operator.token_type {
+                    crate::lexer::TokenType::And => {
+                        // Short-circuit: jump over `right` if `left` is
+                        // falsy. `JumpIfFalse` pops `left` and no opcode
+                        // re-pushes it, so a falsy left-hand side yields
+                        // `false` (also when it was `nil`).
+                        let else_jump = self.emit(OpCode::JumpIfFalse(0), operator.clone());
+                        self.compile_expr(right)?;
+                        let end_jump = self.emit(OpCode::Jump(0), operator.clone());
+                        let else_start = self.scope().chunk.next_index();
+                        self.scope_mut().chunk.patch(else_jump, OpCode::JumpIfFalse(else_start));
+                        self.emit(OpCode::False, operator.clone());
+                        let end = self.scope().chunk.next_index();
+                        self.scope_mut().chunk.patch(end_jump, OpCode::Jump(end));
+                    }
+                    crate::lexer::TokenType::Or => {
+                        // A truthy `left` skips `right` and yields `true`
+                        // rather than the left value, for the same reason.
+                        let else_jump = self.emit(OpCode::JumpIfFalse(0), operator.clone());
+                        self.emit(OpCode::True, operator.clone());
+                        let end_jump = self.emit(OpCode::Jump(0), operator.clone());
+                        let else_start = self.scope().chunk.next_index();
+                        self.scope_mut().chunk.patch(else_jump, OpCode::JumpIfFalse(else_start));
+                        self.compile_expr(right)?;
+                        let end = self.scope().chunk.next_index();
+                        self.scope_mut().chunk.patch(end_jump, OpCode::Jump(end));
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(
+                            operator.clone(),
+                            "Invalid logical operator.".to_string(),
+                        )
+                        .into())
+                    }
+                }
+                Ok(())
+            }
+            Expr::Call { callee, arguments } => {
+                self.compile_expr(callee)?;
+                for arg in arguments {
+                    self.compile_expr(arg)?;
+                }
+                self.emit(OpCode::Call(arguments.len()), callee.first_token());
+                Ok(())
+            }
+            Expr::Get { name, .. }
+            | Expr::Set { name, .. } => Err(RuntimeError::new(
+                name.clone(),
+                "Property access isn't supported by the bytecode VM backend yet.".to_string(),
+            )
+            .into()),
+            Expr::This(keyword) | Expr::Super { keyword, .. } => Err(RuntimeError::new(
+                keyword.clone(),
+                "'this'/'super' aren't supported by the bytecode VM backend yet.".to_string(),
+            )
+            .into()),
+            Expr::New { .. }
+            | Expr::CustomNew { .. }
+            | Expr::Delete { .. }
+            | Expr::DeleteArray { .. }
+            | Expr::Dereference { .. }
+            | Expr::AddressOf { .. }
+            | Expr::NewArray { .. }
+            | Expr::ArrayAccess { .. }
+            | Expr::IndexSet { .. } => Err(RuntimeError::new(
+                expr.first_token(),
+                "Pointers and arrays aren't supported by the bytecode VM backend yet.".to_string(),
+            )
+            .into()),
+            Expr::Lambda { .. } => Err(RuntimeError::new(
+                expr.first_token(),
+                "Lambdas aren't supported by the bytecode VM backend yet.".to_string(),
+            )
+            .into()),
+            Expr::Pipeline { .. } => Err(RuntimeError::new(
+                expr.first_token(),
+                "Pipeline expressions aren't supported by the bytecode VM backend yet.".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    fn compile_literal(&mut self, token: &Token) -> Result<()> {
+        match &token.token_type {
+            crate::lexer::TokenType::Number(n) => {
+                let constant = self.scope_mut().chunk.add_constant(Value::Number(*n));
+                self.emit(OpCode::Constant(constant), token.clone());
+            }
+            crate::lexer::TokenType::String(s) => {
+                let constant = self
+                    .scope_mut()
+                    .chunk
+                    .add_constant(Value::String(Rc::new(s.clone())));
+                self.emit(OpCode::Constant(constant), token.clone());
+            }
+            crate::lexer::TokenType::True => {
+                self.emit(OpCode::True, token.clone());
+            }
+            crate::lexer::TokenType::False => {
+                self.emit(OpCode::False, token.clone());
+            }
+            crate::lexer::TokenType::Nil => {
+                self.emit(OpCode::Nil, token.clone());
+            }
+            _ => {
+                return Err(RuntimeError::new(token.clone(), "Invalid literal value.".to_string()).into())
+            }
+        }
+        Ok(())
+    }
+}
+
+fn name_eof() -> Token {
+    Token::new(crate::lexer::TokenType::Eof, String::new(), 0)
+}
+
+/// Compiles a parsed program into the `<script>` function the VM runs.
+pub fn compile(statements: &[Stmt]) -> Result<VmFunction> {
+    Compiler::compile(statements)
+}
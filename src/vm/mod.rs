@@ -0,0 +1,21 @@
+//! A bytecode compiler and stack-based virtual machine — an alternative
+//! execution backend to the tree-walking `Interpreter`.
+//!
+//! `compiler::compile` flattens a parsed program into a `Chunk` of
+//! `OpCode`s operating on a value stack, and `Vm::run` executes that chunk
+//! directly instead of recursing over the `Expr`/`Stmt` tree. Selected from
+//! the CLI with `--backend vm` (see `main.rs`); the tree-walker remains the
+//! default.
+//!
+//! This backend is newer and narrower than the tree-walker: see
+//! `compiler`'s module docs for what it doesn't compile yet.
+
+mod chunk;
+mod compiler;
+mod value;
+mod vm;
+
+pub use chunk::{Chunk, OpCode};
+pub use compiler::compile;
+pub use value::{NativeFn, Value, VmFunction};
+pub use vm::Vm;
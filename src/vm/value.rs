@@ -0,0 +1,78 @@
+//! Runtime values for the bytecode VM.
+//!
+//! This is a smaller type than [`crate::parser::Literal`]: the VM backend
+//! doesn't compile classes, arrays/maps, or pointers yet (see
+//! `compiler::compile`'s error cases), so its value set only needs to cover
+//! what bytecode actually gets emitted for.
+
+use std::fmt;
+use std::rc::Rc;
+
+use super::chunk::Chunk;
+
+/// A value the VM can push onto its stack.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Number(f64),
+    String(Rc<String>),
+    Boolean(bool),
+    Nil,
+    Function(Rc<VmFunction>),
+    Native(Rc<NativeFn>),
+}
+
+/// A function compiled to its own `Chunk`. Calling one pushes a new call
+/// frame onto the VM's frame stack rather than recursing the way
+/// `interpreter::Function` does.
+#[derive(Debug)]
+pub struct VmFunction {
+    pub name: String,
+    pub arity: usize,
+    pub chunk: Chunk,
+}
+
+/// A built-in function exposed to VM-compiled code, analogous to
+/// `interpreter::NativeFunction`.
+pub struct NativeFn {
+    pub name: String,
+    pub arity: usize,
+    pub function: Box<dyn Fn(&[Value]) -> crate::error::Result<Value>>,
+}
+
+impl fmt::Debug for NativeFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Nil => write!(f, "nil"),
+            Value::Function(func) => write!(f, "<fn {}>", func.name),
+            Value::Native(native) => write!(f, "<native fn {}>", native.name),
+        }
+    }
+}
+
+impl Value {
+    /// Everything is truthy except `nil` and `false`, matching
+    /// `Literal::is_truthy`.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Boolean(false))
+    }
+
+    /// A short name for the value's type, used in runtime type-error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Boolean(_) => "boolean",
+            Value::Nil => "nil",
+            Value::Function(_) | Value::Native(_) => "function",
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! A stack-based virtual machine that executes `Chunk`s produced by
+//! `compiler::compile`.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::error::{Result, RuntimeError};
+use crate::lexer::Token;
+
+use super::chunk::OpCode;
+use super::value::{NativeFn, Value, VmFunction};
+
+/// One in-flight call: the function it's executing, the index of the next
+/// instruction to run, and where its locals start in the shared value
+/// stack.
+struct CallFrame {
+    function: Rc<VmFunction>,
+    ip: usize,
+    /// Stack index of local slot 0. Also one past the callee's own slot, so
+    /// returning truncates the stack back to `base - 1` before pushing the
+    /// result.
+    base: usize,
+}
+
+/// The bytecode VM. Holds the global variable table across runs the same
+/// way `interpreter::Interpreter::globals` persists across `interpret`
+/// calls from the REPL.
+pub struct Vm {
+    stack: Vec<Value>,
+    frames: Vec<CallFrame>,
+    globals: HashMap<String, Value>,
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        let mut vm = Self {
+            stack: Vec::new(),
+            frames: Vec::new(),
+            globals: HashMap::new(),
+        };
+
+        let clock = NativeFn {
+            name: "clock".to_string(),
+            arity: 0,
+            function: Box::new(|_| {
+                Ok(Value::Number(
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs_f64(),
+                ))
+            }),
+        };
+        vm.globals.insert("clock".to_string(), Value::Native(Rc::new(clock)));
+
+        vm
+    }
+}
+
+impl Vm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a compiled script to completion.
+    pub fn run(&mut self, script: VmFunction) -> Result<()> {
+        // A finished or failed earlier run leaves its frames and values
+        // behind; only `globals` carries over between runs.
+        self.stack.clear();
+        self.frames.clear();
+        self.frames.push(CallFrame {
+            function: Rc::new(script),
+            ip: 0,
+            base: 0,
+        });
+
+        loop {
+            let frame_index = self.frames.len() - 1;
+            let ip = self.frames[frame_index].ip;
+            let op = self.frames[frame_index].function.chunk.code[ip].clone();
+            let token = self.frames[frame_index].function.chunk.tokens[ip].clone();
+            self.frames[frame_index].ip += 1;
+
+            match op {
+                OpCode::Constant(idx) => {
+                    let value = self.frames[frame_index].function.chunk.constants[idx].clone();
+                    self.stack.push(value);
+                }
+                OpCode::Nil => self.stack.push(Value::Nil),
+                OpCode::True => self.stack.push(Value::Boolean(true)),
+                OpCode::False => self.stack.push(Value::Boolean(false)),
+                OpCode::Pop => {
+                    self.stack.pop();
+                }
+                OpCode::GetLocal(slot) => {
+                    let base = self.frames[frame_index].base;
+                    self.stack.push(self.stack[base + slot].clone());
+                }
+                OpCode::SetLocal(slot) => {
+                    let base = self.frames[frame_index].base;
+                    let value = self.peek(0).clone();
+                    self.stack[base + slot] = value;
+                }
+                OpCode::GetGlobal(idx) => {
+                    let name = self.constant_name(frame_index, idx);
+                    match self.globals.get(name.as_str()) {
+                        Some(value) => self.stack.push(value.clone()),
+                        None => {
+                            return Err(RuntimeError::new(
+                                token,
+                                format!("Undefined variable '{}'.", name),
+                            )
+                            .into())
+                        }
+                    }
+                }
+                OpCode::DefineGlobal(idx) => {
+                    let name = self.constant_name(frame_index, idx);
+                    let value = self.stack.pop().expect("value to define");
+                    self.globals.insert(name, value);
+                }
+                OpCode::SetGlobal(idx) => {
+                    let name = self.constant_name(frame_index, idx);
+                    if !self.globals.contains_key(name.as_str()) {
+                        return Err(RuntimeError::new(
+                            token,
+                            format!("Undefined variable '{}'.", name),
+                        )
+                        .into());
+                    }
+                    self.globals.insert(name, self.peek(0).clone());
+                }
+                OpCode::Equal => self.binary_bool(values_equal),
+                OpCode::NotEqual => self.binary_bool(|a, b| !values_equal(a, b)),
+                OpCode::Greater => self.compare(&token, |a, b| a > b)?,
+                OpCode::GreaterEqual => self.compare(&token, |a, b| a >= b)?,
+                OpCode::Less => self.compare(&token, |a, b| a < b)?,
+                OpCode::LessEqual => self.compare(&token, |a, b| a <= b)?,
+                OpCode::Add => self.add(&token)?,
+                OpCode::Subtract => self.numeric_binary(&token, |a, b| a - b)?,
+                OpCode::Multiply => self.numeric_binary(&token, |a, b| a * b)?,
+                OpCode::Divide => {
+                    let b = self.peek(0).clone();
+                    if let Value::Number(b) = b {
+                        if b == 0.0 {
+                            return Err(RuntimeError::new(token, "Division by zero.".to_string()).into());
+                        }
+                    }
+                    self.numeric_binary(&token, |a, b| a / b)?
+                }
+                OpCode::Not => {
+                    let value = self.stack.pop().expect("operand");
+                    self.stack.push(Value::Boolean(!value.is_truthy()));
+                }
+                OpCode::Negate => {
+                    let value = self.stack.pop().expect("operand");
+                    match value {
+                        Value::Number(n) => self.stack.push(Value::Number(-n)),
+                        other => {
+                            return Err(RuntimeError::new(
+                                token,
+                                format!("Operand must be a number, got {}.", other.type_name()),
+                            )
+                            .into())
+                        }
+                    }
+                }
+                OpCode::Print => {
+                    let value = self.stack.pop().expect("value to print");
+                    println!("{}", value);
+                }
+                OpCode::Jump(target) => {
+                    self.frames[frame_index].ip = target;
+                }
+                OpCode::JumpIfFalse(target) => {
+                    let condition = self.stack.pop().expect("condition");
+                    if !condition.is_truthy() {
+                        self.frames[frame_index].ip = target;
+                    }
+                }
+                OpCode::Call(argc) => self.call(argc, &token)?,
+                OpCode::Return => {
+                    let result = self.stack.pop().expect("return value");
+                    if self.frames.len() == 1 {
+                        return Ok(());
+                    }
+                    let frame = self.frames.pop().expect("frame to return from");
+                    self.stack.truncate(frame.base - 1);
+                    self.stack.push(result);
+                }
+            }
+        }
+    }
+
+    fn peek(&self, distance: usize) -> &Value {
+        &self.stack[self.stack.len() - 1 - distance]
+    }
+
+    fn constant_name(&self, frame_index: usize, idx: usize) -> String {
+        match &self.frames[frame_index].function.chunk.constants[idx] {
+            Value::String(s) => s.as_str().to_string(),
+            _ => unreachable!("identifier constants are always strings"),
+        }
+    }
+
+    fn binary_bool(&mut self, op: impl Fn(&Value, &Value) -> bool) {
+        let b = self.stack.pop().expect("rhs");
+        let a = self.stack.pop().expect("lhs");
+        self.stack.push(Value::Boolean(op(&a, &b)));
+    }
+
+    fn compare(&mut self, token: &Token, op: impl Fn(f64, f64) -> bool) -> Result<()> {
+        let b = self.stack.pop().expect("rhs");
+        let a = self.stack.pop().expect("lhs");
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                self.stack.push(Value::Boolean(op(a, b)));
+                Ok(())
+            }
+            (a, b) => Err(RuntimeError::new(
+                token.clone(),
+                format!("Cannot compare {} and {}.", a.type_name(), b.type_name()),
+            )
+            .into()),
+        }
+    }
+
+    fn numeric_binary(&mut self, token: &Token, op: impl Fn(f64, f64) -> f64) -> Result<()> {
+        let b = self.stack.pop().expect("rhs");
+        let a = self.stack.pop().expect("lhs");
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                self.stack.push(Value::Number(op(a, b)));
+                Ok(())
+            }
+            (a, b) => Err(RuntimeError::new(
+                token.clone(),
+                format!("Operands must be numbers, got {} and {}.", a.type_name(), b.type_name()),
+            )
+            .into()),
+        }
+    }
+
+    fn add(&mut self, token: &Token) -> Result<()> {
+        let b = self.stack.pop().expect("rhs");
+        let a = self.stack.pop().expect("lhs");
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                self.stack.push(Value::Number(a + b));
+                Ok(())
+            }
+            (Value::String(a), Value::String(b)) => {
+                self.stack.push(Value::String(Rc::new(format!("{}{}", a, b))));
+                Ok(())
+            }
+            (a, b) => Err(RuntimeError::new(
+                token.clone(),
+                format!("Cannot add {} and {}.", a.type_name(), b.type_name()),
+            )
+            .into()),
+        }
+    }
+
+    fn call(&mut self, argc: usize, token: &Token) -> Result<()> {
+        let callee = self.peek(argc).clone();
+        match callee {
+            Value::Function(function) => {
+                if argc != function.arity {
+                    return Err(RuntimeError::new(
+                        token.clone(),
+                        format!("Expected {} arguments but got {}.", function.arity, argc),
+                    )
+                    .into());
+                }
+                let base = self.stack.len() - argc;
+                self.frames.push(CallFrame {
+                    function,
+                    ip: 0,
+                    base,
+                });
+                Ok(())
+            }
+            Value::Native(native) => {
+                if argc != native.arity {
+                    return Err(RuntimeError::new(
+                        token.clone(),
+                        format!("Expected {} arguments but got {}.", native.arity, argc),
+                    )
+                    .into());
+                }
+                let args_start = self.stack.len() - argc;
+                let args: Vec<Value> = self.stack.split_off(args_start);
+                self.stack.pop(); // the callee itself
+                let result = (native.function)(&args)?;
+                self.stack.push(result);
+                Ok(())
+            }
+            other => Err(RuntimeError::new(
+                token.clone(),
+                format!("Can only call functions, got {}.", other.type_name()),
+            )
+            .into()),
+        }
+    }
+}
+
+/// Equality for VM values. Functions are never equal to anything, the same
+/// stance `Literal::is_equal` takes for `Callable`/`Instance`/`Class`.
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Nil, Value::Nil) => true,
+        (Value::Boolean(a), Value::Boolean(b)) => a == b,
+        (Value::Number(a), Value::Number(b)) => a == b,
+        (Value::String(a), Value::String(b)) => a == b,
+        _ => false,
+    }
+}